@@ -0,0 +1,564 @@
+//! non-interactive command line mode, so tests can be run from scripts
+//!
+//! ```text
+//! chi_squared oe --observed 10,12,8 --expected 10,10,10
+//! chi_squared oe --observed 315,108,101,32 --ratio 9:3:3:1
+//! chi_squared binomial --n 5 --p 0.3 --observed 12,25,20,18,10,15
+//! chi_squared poisson --lambda 1.2 --observed 30,36,20,9,5
+//! chi_squared poisson --frequencies weighted --observed 36.8,36.8,18.4,8
+//! chi_squared geometric --p 0.4 --observed 40,25,15,10,10
+//! chi_squared negative-binomial --observed 70,38,22,14,9,7,5,10
+//! chi_squared normal --classes 0-10,10-20,20-30,30-40 --observed 8,30,42,20
+//! chi_squared exponential --classes 0-5,5-10,10-20,20-40 --observed 45,25,20,10
+//! chi_squared contingency --file t.csv
+//! ```
+//!
+//! contingency table files can be CSV or TSV, with optional row and column labels
+//! (see [`crate::import`])
+
+use std::collections::HashMap;
+
+use crate::{import, json};
+use crate::screens::{
+    print_observed_expected, print_binomial, print_poisson, print_geometric, print_negative_binomial,
+    print_normal, print_uniform, print_exponential, print_contingency_table
+};
+use crate::stats::{
+    self, BinomialP, ExpectedFrequencies, ExponentialRate, Frequencies, GeometricP, MinimumExpected,
+    NegativeBinomialP, NegativeBinomialR, NormalMean, NormalSd, PoissonMean, UniformRange
+};
+use crate::Significance;
+
+pub const USAGE: &str = "\
+usage:
+  chi_squared                    start the interactive calculator
+  chi_squared oe --observed <list> --expected <list|uniform> [--labels <list>]
+  chi_squared oe --observed <list> --ratio <a:b:...> [--labels <list>]
+  chi_squared oe --observed <list> --proportions <list> [--labels <list>]
+  chi_squared binomial --n <trials> [--p <probability>] --observed <list> [--values <list>]
+  chi_squared poisson [--lambda <mean>] --observed <list> [--values <list>]
+  chi_squared geometric [--p <probability>] --observed <list> [--values <list>]
+  chi_squared negative-binomial [--r <successes>] [--p <probability>] --observed <list>
+                                 [--values <list>]
+  chi_squared normal [--mean <μ>] [--sd <σ>] --classes <list> --observed <list>
+  chi_squared uniform [--a <lower> --b <upper>] --classes <list> --observed <list>
+  chi_squared exponential [--lambda <rate>] --classes <list> --observed <list>
+  chi_squared contingency --file <path>
+
+options:
+  --significance <percent>       significance level of the test (default 5)
+  --format <table|json>          how to print the result (default table)
+  --yates <on|off>               yates' correction for 2 class O vs E tests
+                                 and 2x2 contingency tables (default on)
+  --exact <auto|on|off>          fisher's exact test for contingency tables
+                                 (default auto, when any expected is below 5)
+  --merge <auto|off>             merge adjacent classes, rows or columns of O vs E
+                                 and contingency tests until no expected is below 5
+                                 (default off)
+  --min-expected <number|cochran>
+                                 smallest expected frequency the classes of fitted
+                                 distributions are grouped up to, or cochran's rule
+                                 (default 5)
+  --frequencies <counts|weighted>
+                                 whether observed frequencies must be whole counts,
+                                 or can be any number that isn't negative, such as
+                                 weighted or rescaled frequencies (default counts)
+
+lists are comma separated, e.g. --observed 10,12,8
+geometric frequencies are for 1, 2, 3, ... trials, the last for that many or more
+poisson and negative binomial frequencies are for 0, 1, 2, ..., the last for that many or more
+--values gives the value of X of each observed frequency instead, in any order, with
+values left out taken as 0 (e.g. --values 0,1,2,>=4)
+normal, uniform and exponential classes are intervals like 10-20, with the first
+and last taken as open ended
+leave out --p, --r, --lambda, --mean or --sd to estimate them from the data, and --a and
+--b to use the range of the classes";
+
+/// runs the test described by the command line arguments (not including the program name)
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (command, flags) = match args.split_first() {
+        Some((command, flags)) => (command.as_str(), parse_flags(flags)?),
+        None => return Err(String::from("no command given")),
+    };
+
+    if command == "help" || command == "--help" || command == "-h" {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let accepted = match command_flags(command) {
+        Some(accepted) => accepted,
+        None => return Err(format!("unknown command '{command}'")),
+    };
+    let mut unknown: Vec<&String> = flags.keys().filter(|name| !accepted.contains(&name.as_str())).collect();
+    unknown.sort();
+    if let Some(name) = unknown.first() {
+        return Err(format!("{command} doesn't take --{name}"));
+    }
+
+    let significance = match flags.get("significance") {
+        Some(percentage) => {
+            let percentage = parse_number(percentage, "significance")?;
+            if !(0. < percentage && percentage < 100.) {
+                return Err(String::from("--significance must be between 0 and 100"));
+            }
+            Significance::new(percentage)
+        },
+        None => Significance::new(5.),
+    };
+
+    let format = match flags.get("format").map(|format| format.as_str()) {
+        Some("table") | None => Format::Table,
+        Some("json") => Format::Json,
+        Some(format) => return Err(format!("unknown format '{format}'")),
+    };
+    let yates = match flags.get("yates").map(|yates| yates.as_str()) {
+        Some("on") | None => true,
+        Some("off") => false,
+        Some(yates) => return Err(format!("--yates must be on or off, not '{yates}'")),
+    };
+    let exact = match flags.get("exact").map(|exact| exact.as_str()) {
+        Some("auto") | None => None,
+        Some("on") => Some(true),
+        Some("off") => Some(false),
+        Some(exact) => return Err(format!("--exact must be auto, on or off, not '{exact}'")),
+    };
+    let merge = match flags.get("merge").map(|merge| merge.as_str()) {
+        Some("auto") => true,
+        Some("off") | None => false,
+        Some(merge) => return Err(format!("--merge must be auto or off, not '{merge}'")),
+    };
+    let minimum = match flags.get("min-expected").map(|minimum| minimum.as_str()) {
+        Some("cochran") => MinimumExpected::Cochran,
+        Some(minimum) => {
+            let minimum = parse_number(minimum, "min-expected")?;
+            if !(minimum > 0. && minimum.is_finite()) {
+                return Err(String::from("--min-expected must be a positive number or cochran"));
+            }
+            MinimumExpected::AtLeast(minimum)
+        },
+        None => MinimumExpected::default(),
+    };
+    let frequencies = match flags.get("frequencies").map(|frequencies| frequencies.as_str()) {
+        Some("counts") | None => Frequencies::Counts,
+        Some("weighted") => Frequencies::Weighted,
+        Some(frequencies) => return Err(format!("--frequencies must be counts or weighted, not '{frequencies}'")),
+    };
+    let output = Output { significance, format, yates, exact, merge, minimum, frequencies };
+
+    match command {
+        "oe" => observed_expected(&flags, output),
+        "binomial" => binomial(&flags, output),
+        "poisson" => poisson(&flags, output),
+        "geometric" => geometric(&flags, output),
+        "negative-binomial" => negative_binomial(&flags, output),
+        "normal" => normal(&flags, output),
+        "uniform" => uniform(&flags, output),
+        "exponential" => exponential(&flags, output),
+        "contingency" => contingency_table(&flags, output),
+        _ => unreachable!("commands are checked against command_flags"),
+    }
+}
+
+/// the options a command takes, or `None` if there's no such command
+fn command_flags(command: &str) -> Option<&'static [&'static str]> {
+    let flags: &[&str] = match command {
+        "oe" => &["observed", "expected", "ratio", "proportions", "labels", "yates", "merge", "frequencies", "significance", "format"],
+        "binomial" => &["n", "p", "observed", "values", "min-expected", "frequencies", "significance", "format"],
+        "poisson" => &["lambda", "observed", "values", "min-expected", "frequencies", "significance", "format"],
+        "geometric" => &["p", "observed", "values", "min-expected", "frequencies", "significance", "format"],
+        "negative-binomial" => &["r", "p", "observed", "values", "min-expected", "frequencies", "significance", "format"],
+        "normal" => &["mean", "sd", "classes", "observed", "min-expected", "frequencies", "significance", "format"],
+        "uniform" => &["a", "b", "classes", "observed", "min-expected", "frequencies", "significance", "format"],
+        "exponential" => &["lambda", "classes", "observed", "min-expected", "frequencies", "significance", "format"],
+        "contingency" => &["file", "yates", "exact", "merge", "frequencies", "significance", "format"],
+        _ => return None,
+    };
+
+    Some(flags)
+}
+
+enum Format {
+    Table,
+    Json,
+}
+
+/// how a result should be printed
+struct Output {
+    significance: Significance,
+    format: Format,
+    /// whether yates' correction is applied when there's 1 degree of freedom
+    yates: bool,
+    /// whether fisher's exact test is run on contingency tables, or `None` to leave it to the expecteds
+    exact: Option<bool>,
+    /// whether low expected frequencies are merged away before testing
+    merge: bool,
+    /// the rule fitted distributions' classes are grouped to meet
+    minimum: MinimumExpected,
+    /// whether observed frequencies must be whole counts
+    frequencies: Frequencies,
+}
+
+fn observed_expected(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let observed = parse_frequencies(required(flags, "observed")?, "observed", output.frequencies)?;
+    let (expected_frequencies, expected_flag) = match (flags.get("expected"), flags.get("ratio"), flags.get("proportions")) {
+        (Some(expected), None, None) if expected == "uniform" => (ExpectedFrequencies::Uniform, "expected"),
+        (Some(expected), None, None) => (ExpectedFrequencies::Given(parse_list(expected, "expected")?), "expected"),
+        (None, Some(ratio), None) => (ExpectedFrequencies::Ratio(stats::parse_ratio(ratio).map_err(|e| format!("--ratio: {e}"))?), "ratio"),
+        (None, None, Some(proportions)) => (
+            ExpectedFrequencies::Proportions(stats::parse_proportions(proportions).map_err(|e| format!("--proportions: {e}"))?),
+            "proportions"
+        ),
+        _ => return Err(String::from("give one of --expected, --ratio or --proportions")),
+    };
+    let expected = expected_frequencies.expecteds(&observed).map_err(|e| format!("--{expected_flag}: {e}"))?;
+
+    let column_labels = match flags.get("labels") {
+        Some(labels) => labels.split(',').map(|label| label.trim().to_string()).collect(),
+        None => (1..observed.len()+1).map(|i| i.to_string()).collect::<Vec<String>>(),
+    };
+    if column_labels.len() != observed.len() {
+        return Err(String::from("--labels must be the same length as --observed"));
+    }
+
+    let mut result = stats::observed_expected(&observed, &expected).map_err(|e| format!("--observed: {e}"))?;
+    result.class_labels = column_labels;
+    if output.merge {
+        result = result.merge_low_expecteds();
+    }
+    result.set_yates_correction(output.yates);
+    match output.format {
+        Format::Table => print_observed_expected(&result, &expected_frequencies, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn binomial(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let n: usize = required(flags, "n")?
+        .parse()
+        .map_err(|_| String::from("--n must be a whole number"))?;
+    if n >= stats::MAX_VALUES {
+        return Err(format!("--n can be at most {}", stats::MAX_VALUES - 1));
+    }
+    let p = match flags.get("p") {
+        Some(p) => {
+            let p = parse_number(p, "p")?;
+            if !(0. ..=1.).contains(&p) {
+                return Err(String::from("--p must be between 0 and 1"));
+            }
+            BinomialP::P(p)
+        },
+        None => BinomialP::Estimate,
+    };
+    let observed = parse_value_frequencies(flags, 0, Some(n), output.frequencies)?;
+    if observed.len() != n+1 {
+        return Err(format!("--observed needs a frequency for each value 0 to {n} ({} values)", n+1));
+    }
+
+    let result = stats::binomial(n, p, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_binomial(&result, p, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn poisson(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let mean = match flags.get("lambda") {
+        Some(mean) => {
+            let mean = parse_number(mean, "lambda")?;
+            if mean < 0. {
+                return Err(String::from("--lambda must not be negative"));
+            }
+            PoissonMean::Mean(mean)
+        },
+        None => PoissonMean::Estimate,
+    };
+    let observed = parse_value_frequencies(flags, 0, None, output.frequencies)?;
+
+    let result = stats::poisson(mean, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_poisson(&result, mean, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn geometric(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let p = match flags.get("p") {
+        Some(p) => {
+            let p = parse_number(p, "p")?;
+            if !(0. < p && p <= 1.) {
+                return Err(String::from("--p must be greater than 0 and at most 1"));
+            }
+            GeometricP::P(p)
+        },
+        None => GeometricP::Estimate,
+    };
+    let observed = parse_value_frequencies(flags, 1, None, output.frequencies)?;
+
+    let result = stats::geometric(p, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_geometric(&result, p, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn negative_binomial(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let r = match flags.get("r") {
+        Some(r) => {
+            let r = parse_number(r, "r")?;
+            if r <= 0. {
+                return Err(String::from("--r must be positive"));
+            }
+            NegativeBinomialR::R(r)
+        },
+        None => NegativeBinomialR::Estimate,
+    };
+    let p = match flags.get("p") {
+        Some(p) => {
+            let p = parse_number(p, "p")?;
+            if !(0. < p && p <= 1.) {
+                return Err(String::from("--p must be greater than 0 and at most 1"));
+            }
+            NegativeBinomialP::P(p)
+        },
+        None => NegativeBinomialP::Estimate,
+    };
+    let observed = parse_value_frequencies(flags, 0, None, output.frequencies)?;
+
+    let result = stats::negative_binomial(r, p, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_negative_binomial(&result, r, p, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn normal(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let mean = match flags.get("mean") {
+        Some(mean) => NormalMean::Mean(parse_number(mean, "mean")?),
+        None => NormalMean::Estimate,
+    };
+    let sd = match flags.get("sd") {
+        Some(sd) => {
+            let sd = parse_number(sd, "sd")?;
+            if sd <= 0. {
+                return Err(String::from("--sd must be positive"));
+            }
+            NormalSd::Sd(sd)
+        },
+        None => NormalSd::Estimate,
+    };
+    let (classes, observed) = parse_class_intervals(flags, output.frequencies)?;
+
+    let result = stats::normal(mean, sd, &classes, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_normal(&result, mean, sd, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn uniform(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let range = match (flags.get("a"), flags.get("b")) {
+        (Some(a), Some(b)) => {
+            let (a, b) = (parse_number(a, "a")?, parse_number(b, "b")?);
+            if a >= b {
+                return Err(String::from("--a must be below --b"));
+            }
+            UniformRange::Range(a, b)
+        },
+        (None, None) => UniformRange::Classes,
+        _ => return Err(String::from("--a and --b must be given together")),
+    };
+    let (classes, observed) = parse_class_intervals(flags, output.frequencies)?;
+
+    let result = stats::uniform(range, &classes, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_uniform(&result, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn exponential(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let rate = match flags.get("lambda") {
+        Some(rate) => {
+            let rate = parse_number(rate, "lambda")?;
+            if rate <= 0. {
+                return Err(String::from("--lambda must be positive"));
+            }
+            ExponentialRate::Rate(rate)
+        },
+        None => ExponentialRate::Estimate,
+    };
+    let (classes, observed) = parse_class_intervals(flags, output.frequencies)?;
+
+    let result = stats::exponential(rate, &classes, &observed, output.minimum).map_err(|e| format!("--observed: {e}"))?;
+    match output.format {
+        Format::Table => print_exponential(&result, rate, output.significance),
+        Format::Json => println!("{}", json::goodness_of_fit(&result, output.significance)),
+    }
+    Ok(())
+}
+
+fn contingency_table(flags: &HashMap<String, String>, output: Output) -> Result<(), String> {
+    let path = required(flags, "file")?;
+    let table = import::read_table(path).map_err(|e| format!("--file: {e}"))?;
+
+    let observed = table.cells.iter()
+        .map(|row| row.iter().map(|cell| parse_frequency(cell, "file", output.frequencies)).collect())
+        .collect::<Result<Vec<Vec<f64>>, String>>()?;
+
+    let mut result = stats::contingency_test(&observed).map_err(|e| format!("--file: {e}"))?;
+    if let Some(labels) = table.column_labels {result.column_labels = labels}
+    if let Some(labels) = table.row_labels {result.row_labels = labels}
+    if output.merge {
+        result = result.merge_low_expecteds();
+    }
+    result.set_yates_correction(output.yates);
+    // by default fisher's exact test is only run if the final table has low expecteds
+    result.set_exact_test(output.exact.unwrap_or(result.has_low_expecteds()));
+    if let (Some(true), Some(error)) = (output.exact, &result.exact_error) {
+        return Err(format!("--exact: {error}"));
+    }
+    let corner_label = table.corner_label.unwrap_or(String::from("---"));
+
+    match output.format {
+        Format::Table => print_contingency_table(&result, &corner_label, output.significance),
+        Format::Json => println!("{}", json::contingency_table(&result, output.significance)),
+    }
+    Ok(())
+}
+
+/// reads `--name value` (or `--name=value`) pairs into a map of name to value
+fn parse_flags(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut flags = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let name = match arg.strip_prefix("--") {
+            Some(name) => name,
+            None => return Err(format!("unexpected argument '{arg}'")),
+        };
+
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name.to_string(), value.to_string()),
+            None => match args.next() {
+                Some(value) => (name.to_string(), value.to_string()),
+                None => return Err(format!("--{name} needs a value")),
+            },
+        };
+        flags.insert(name, value);
+    }
+
+    Ok(flags)
+}
+
+fn required<'a>(flags: &'a HashMap<String, String>, name: &str) -> Result<&'a str, String> {
+    flags.get(name).map(|value| value.as_str()).ok_or(format!("--{name} is required"))
+}
+
+fn parse_number(value: &str, name: &str) -> Result<f64, String> {
+    value.trim().parse().map_err(|_| format!("--{name}: '{value}' is not a number"))
+}
+
+fn parse_list(list: &str, name: &str) -> Result<Vec<f64>, String> {
+    list.split(',').map(|value| parse_number(value, name)).collect()
+}
+
+/// observed frequencies must be whole counts unless they're weighted, and never negative
+fn parse_frequency(value: &str, name: &str, frequencies: Frequencies) -> Result<f64, String> {
+    frequencies.parse(value).map_err(|e| format!("--{name}: {e}"))
+}
+
+fn parse_frequencies(list: &str, name: &str, frequencies: Frequencies) -> Result<Vec<f64>, String> {
+    list.split(',').map(|value| parse_frequency(value, name, frequencies)).collect()
+}
+
+/// the --observed frequency of each value of X from `first_value` up, either in order or
+/// labelled by --values (in any order, with any values left out having a frequency of 0)
+fn parse_value_frequencies(
+    flags: &HashMap<String, String>,
+    first_value: usize,
+    last_value: Option<usize>,
+    frequencies: Frequencies
+) -> Result<Vec<f64>, String> {
+    let observed = parse_frequencies(required(flags, "observed")?, "observed", frequencies)?;
+    match flags.get("values") {
+        Some(values) => {
+            let values: Vec<String> = values.split(',').map(|value| value.trim().to_string()).collect();
+            if values.len() != observed.len() {
+                return Err(String::from("--values and --observed must be the same length"));
+            }
+            stats::value_frequencies(&values, &observed, first_value, last_value).map_err(|e| format!("--values: {e}"))
+        },
+        None => Ok(observed),
+    }
+}
+
+/// the --classes intervals and --observed frequencies of grouped continuous data
+fn parse_class_intervals(flags: &HashMap<String, String>, frequencies: Frequencies) -> Result<(Vec<String>, Vec<f64>), String> {
+    let classes: Vec<String> = required(flags, "classes")?.split(',').map(|class| class.trim().to_string()).collect();
+    let intervals = classes.iter()
+        .map(|class| stats::parse_class_interval(class))
+        .collect::<Result<Vec<(f64, f64)>, String>>()
+        .map_err(|e| format!("--classes: {e}"))?;
+    if intervals.windows(2).any(|pair| pair[1].0 < pair[0].1) {
+        return Err(String::from("--classes: class intervals must be in order and not overlap"));
+    }
+    let observed = parse_frequencies(required(flags, "observed")?, "observed", frequencies)?;
+    if classes.len() != observed.len() {
+        return Err(String::from("--classes and --observed must be the same length"));
+    }
+
+    Ok((classes, observed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn flags_with_and_without_equals() {
+        let flags = parse_flags(&args(&["--n", "5", "--p=0.3"])).unwrap();
+        assert_eq!(flags["n"], "5");
+        assert_eq!(flags["p"], "0.3");
+        assert!(parse_flags(&args(&["--n"])).is_err());
+        assert!(parse_flags(&args(&["5"])).is_err());
+    }
+
+    #[test]
+    fn invalid_commands_are_errors() {
+        assert!(run(&args(&["unknown"])).is_err());
+        assert!(run(&args(&["oe", "--observed", "1,2"])).is_err());
+        assert!(run(&args(&["binomial", "--n", "2", "--observed", "1,2"])).is_err());
+        assert!(run(&args(&["poisson", "--observed", "10.5,4,2"])).is_err());
+        assert!(run(&args(&["poisson", "--observed", "10,4,2", "--frequencies", "rounded"])).is_err());
+        assert!(run(&args(&["binomial", "--n", "3", "--p", "0", "--observed", "5,1,1,1", "--min-expected", "0"])).is_err());
+        assert!(run(&args(&["poisson", "--observed", "10,4", "--values", "0,99999999999"])).is_err());
+        assert!(run(&args(&["binomial", "--n", "18446744073709551615", "--observed", "5,1"])).is_err());
+        assert!(run(&args(&["oe", "--observed", "-5,20,10", "--expected", "10,10,10"])).is_err());
+        assert!(run(&args(&["oe", "--observed", "5.5,20,10", "--expected", "10,10,10"])).is_err());
+        assert!(run(&args(&["oe", "--observed", "5.5,20,10", "--expected", "10,10,10", "--frequencies", "weighted", "--format", "json"])).is_ok());
+    }
+
+    #[test]
+    fn unknown_flags_are_errors() {
+        // a misspelt flag would otherwise estimate λ instead
+        let error = run(&args(&["poisson", "--lamda", "1.2", "--observed", "30,36,20,9,5"])).unwrap_err();
+        assert_eq!(error, "poisson doesn't take --lamda");
+        assert!(run(&args(&["oe", "--observed", "10,12,8", "--expected", "10,10,10", "--exact", "on"])).is_err());
+
+        let error = run(&args(&["normal", "--classes", "0-10,5-20", "--observed", "10,12"])).unwrap_err();
+        assert!(error.starts_with("--classes: "));
+        let error = run(&args(&["poisson", "--observed", "0,0,0"])).unwrap_err();
+        assert!(error.starts_with("--observed: "));
+    }
+}
@@ -0,0 +1,340 @@
+//! probability distribution functions, both for the expected frequencies
+//! of the fitted models and to turn a X² statistic into p-values and critical values
+
+/// coefficients for the lanczos approximation (g = 7, n = 9)
+const LANCZOS_G: f64 = 7.;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// max iterations for the incomplete gamma series/continued fraction
+const MAX_ITERATIONS: usize = 1000;
+/// relative accuracy the incomplete gamma function stops at
+const EPSILON: f64 = 1e-15;
+/// smallest number used to avoid dividing by 0 in the continued fraction
+const FP_MIN: f64 = 1e-300;
+
+/// natural log of the gamma function, using the lanczos approximation
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // reflection formula so the approximation stays accurate
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).abs().ln() - ln_gamma(1. - x);
+    }
+
+    let x = x - 1.;
+    let mut sum = LANCZOS_COEFFICIENTS[0];
+    for (i, coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+    let t = x + LANCZOS_G + 0.5;
+
+    0.5 * (2. * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// natural log of n!
+pub fn ln_factorial(n: u64) -> f64 {
+    ln_gamma(n as f64 + 1.)
+}
+
+/// natural log of nCr
+pub fn ln_choose(n: u64, r: u64) -> f64 {
+    assert!(n >= r);
+    ln_factorial(n) - ln_factorial(r) - ln_factorial(n - r)
+}
+
+/// P(X = x) for X ~ B(n, p), worked out in log space so large n doesn't overflow
+pub fn binomial_pmf(n: u64, p: f64, x: u64) -> f64 {
+    if x > n {
+        return 0.;
+    }
+    // avoid ln(0) at the edges of p
+    if p == 0. {
+        return if x == 0 { 1. } else { 0. };
+    }
+    if p == 1. {
+        return if x == n { 1. } else { 0. };
+    }
+
+    (ln_choose(n, x) + x as f64 * p.ln() + (n - x) as f64 * (1. - p).ln()).exp()
+}
+
+/// P(X = x) for X ~ Po(λ), worked out in log space so large x and λ don't overflow
+pub fn poisson_pmf(mean: f64, x: u64) -> f64 {
+    if mean == 0. {
+        return if x == 0 { 1. } else { 0. };
+    }
+
+    (-mean + x as f64 * mean.ln() - ln_factorial(x)).exp()
+}
+
+/// P(X = x) for X ~ Geo(p), the number of trials up to and including the first success
+pub fn geometric_pmf(p: f64, x: u64) -> f64 {
+    if x == 0 {
+        return 0.;
+    }
+
+    (1. - p).powf((x - 1) as f64) * p
+}
+
+/// P(X = x) for X ~ NB(r, p), the number of failures before the rth success.
+/// r doesn't have to be whole, so the coefficient uses the gamma function
+pub fn negative_binomial_pmf(r: f64, p: f64, x: u64) -> f64 {
+    if p == 1. {
+        return if x == 0 { 1. } else { 0. };
+    }
+
+    let x_f = x as f64;
+    (ln_gamma(x_f + r) - ln_gamma(r) - ln_factorial(x) + r * p.ln() + x_f * (1. - p).ln()).exp()
+}
+
+/// P(X <= x) for X ~ N(μ, σ²), using erf(z) = P(1/2, z²) so the tails stay accurate
+pub fn normal_cdf(mean: f64, sd: f64, x: f64) -> f64 {
+    assert!(sd > 0., "standard deviation must be positive");
+    let z = (x - mean) / sd;
+    if z < 0. {
+        0.5 * regularized_gamma_q(0.5, z * z / 2.)
+    } else {
+        0.5 + 0.5 * regularized_gamma_p(0.5, z * z / 2.)
+    }
+}
+
+/// P(X <= x) for X ~ U(a, b)
+pub fn uniform_cdf(a: f64, b: f64, x: f64) -> f64 {
+    assert!(a < b, "the lower end of the range must be below the upper end");
+    ((x - a) / (b - a)).clamp(0., 1.)
+}
+
+/// P(X <= x) for X ~ Exp(λ)
+pub fn exponential_cdf(rate: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+
+    -(-rate * x).exp_m1()
+}
+
+/// regularized lower incomplete gamma function P(a, x)
+pub fn regularized_gamma_p(a: f64, x: f64) -> f64 {
+    assert!(a > 0., "gamma shape must be positive");
+    if x <= 0. {
+        return 0.;
+    }
+
+    if x < a + 1. {
+        gamma_series(a, x)
+    } else {
+        1. - gamma_continued_fraction(a, x)
+    }
+}
+
+/// regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x)
+pub fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    assert!(a > 0., "gamma shape must be positive");
+    if x <= 0. {
+        return 1.;
+    }
+
+    if x < a + 1. {
+        1. - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+/// P(a, x) by its series representation (converges quickly for x < a + 1)
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut term = 1. / a;
+    let mut sum = term;
+    let mut denominator = a;
+    for _ in 0..MAX_ITERATIONS {
+        denominator += 1.;
+        term *= x / denominator;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// Q(a, x) by its continued fraction (modified lentz's method, converges quickly for x >= a + 1)
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1. - a;
+    let mut c = 1. / FP_MIN;
+    let mut d = 1. / b;
+    let mut h = d;
+    for i in 1..MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.;
+        d = an * d + b;
+        if d.abs() < FP_MIN {
+            d = FP_MIN;
+        }
+        c = b + an / c;
+        if c.abs() < FP_MIN {
+            c = FP_MIN;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// the chi-squared distribution with a given number of degrees of freedom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    df: usize,
+}
+
+impl ChiSquared {
+    /// # Panics
+    /// if `df` is 0, as there's no distribution to test a statistic against
+    pub fn new(df: usize) -> ChiSquared {
+        assert!(df > 0, "there must be at least 1 degree of freedom");
+        ChiSquared { df }
+    }
+
+    pub fn df(&self) -> usize {
+        self.df
+    }
+
+    /// P(X² <= x)
+    pub fn cdf(&self, x: f64) -> f64 {
+        regularized_gamma_p(self.df as f64 / 2., x / 2.)
+    }
+
+    /// P(X² > x), the p-value of a statistic x
+    pub fn sf(&self, x: f64) -> f64 {
+        regularized_gamma_q(self.df as f64 / 2., x / 2.)
+    }
+
+    /// the value x where P(X² <= x) = p
+    pub fn inverse_cdf(&self, p: f64) -> f64 {
+        assert!((0. ..=1.).contains(&p), "probability must be between 0 and 1");
+        if p == 0. {
+            return 0.;
+        }
+        if p == 1. {
+            return f64::INFINITY;
+        }
+
+        // find an upper bound then bisect down to the value
+        let mut lower = 0.;
+        let mut upper = self.df as f64 + 1.;
+        while self.cdf(upper) < p {
+            lower = upper;
+            upper *= 2.;
+        }
+        for _ in 0..200 {
+            let middle = (lower + upper) / 2.;
+            if self.cdf(middle) < p {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+            if upper - lower <= EPSILON * upper {
+                break;
+            }
+        }
+
+        (lower + upper) / 2.
+    }
+
+    /// the value the statistic must exceed to be significant at the given level
+    pub fn critical_value(&self, significance: f64) -> f64 {
+        self.inverse_cdf(1. - significance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64, tolerance: f64) {
+        assert!((a - b).abs() < tolerance, "{a} is not close to {b}");
+    }
+
+    #[test]
+    fn ln_gamma_matches_factorials() {
+        assert_close(ln_gamma(1.), 0., 1e-12);
+        assert_close(ln_gamma(6.), 120_f64.ln(), 1e-12);
+        assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12);
+    }
+
+    #[test]
+    fn ln_factorial_works() {
+        assert_close(ln_factorial(5).exp(), 120., 1e-9);
+        assert_close(ln_factorial(20).exp(), 2_432_902_008_176_640_000., 1e4);
+    }
+
+    #[test]
+    fn binomial_pmf_known_values() {
+        assert_close(binomial_pmf(5, 0.3, 2), 0.3087, 1e-12);
+        assert_close(binomial_pmf(200, 0.5, 100), 0.056_348_479_009_256_42, 1e-12);
+        assert_eq!(binomial_pmf(10, 0., 0), 1.);
+        assert_eq!(binomial_pmf(10, 1., 9), 0.);
+
+        let total: f64 = (0..501).map(|x| binomial_pmf(500, 0.37, x)).sum();
+        assert_close(total, 1., 1e-10);
+    }
+
+    #[test]
+    fn poisson_pmf_known_values() {
+        assert_close(poisson_pmf(1.2, 3), 0.086_743_933_030_714_2, 1e-12);
+        assert_close(poisson_pmf(100., 100), 0.039_860_996_809_148_83, 1e-12);
+        assert_eq!(poisson_pmf(0., 0), 1.);
+    }
+
+    #[test]
+    fn geometric_pmf_known_values() {
+        assert_close(geometric_pmf(0.25, 3), 0.140_625, 1e-12);
+        assert_eq!(geometric_pmf(0.25, 0), 0.);
+        assert_eq!(geometric_pmf(1., 1), 1.);
+    }
+
+    #[test]
+    fn negative_binomial_pmf_known_values() {
+        // with r = 1 it's the geometric distribution counting failures
+        assert_close(negative_binomial_pmf(1., 0.25, 2), geometric_pmf(0.25, 3), 1e-12);
+        assert_close(negative_binomial_pmf(2.5, 0.4, 3), 0.143_440_914_665_237_62, 1e-12);
+        let total: f64 = (0..200).map(|x| negative_binomial_pmf(3., 0.3, x)).sum();
+        assert_close(total, 1., 1e-10);
+    }
+
+    #[test]
+    fn normal_cdf_known_values() {
+        assert_close(normal_cdf(0., 1., 0.), 0.5, 1e-15);
+        assert_close(normal_cdf(0., 1., 1.959963984540054), 0.975, 1e-12);
+        assert_close(normal_cdf(10., 2., 7.), 0.066_807_201_268_858_09, 1e-12);
+        assert_close(normal_cdf(0., 1., -8.), 6.220_960_574_271_819e-16, 1e-25);
+    }
+
+    #[test]
+    fn chi_squared_critical_values() {
+        assert_close(ChiSquared::new(1).critical_value(0.05), 3.841458820694124, 1e-9);
+        assert_close(ChiSquared::new(4).critical_value(0.01), 13.276704135987622, 1e-9);
+        assert_close(ChiSquared::new(10).critical_value(0.05), 18.307038053275146, 1e-9);
+    }
+
+    #[test]
+    fn chi_squared_p_values() {
+        assert_close(ChiSquared::new(1).sf(3.841458820694124), 0.05, 1e-12);
+        assert_close(ChiSquared::new(2).sf(2.), (-1_f64).exp(), 1e-12);
+        assert_close(ChiSquared::new(30).cdf(29.336), 0.5, 1e-4);
+    }
+}
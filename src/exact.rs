@@ -0,0 +1,164 @@
+//! fisher's exact test for contingency tables, for when expected frequencies are too
+//! small for the X² approximation. r×c tables use the freeman-halton extension, which
+//! enumerates every table with the same row and column totals
+
+use crate::distribution::ln_factorial;
+
+/// the most cells that will be filled in while enumerating tables before giving up,
+/// to keep the test responsive
+const MAX_STEPS: usize = 5_000_000;
+/// relative tolerance when comparing table probabilities to the observed table's
+const TOLERANCE: f64 = 1e-7;
+
+/// the two-sided exact p-value of a table of counts: the total probability of every
+/// table with the same row and column totals that's no more likely than the observed one
+pub fn fisher_exact(observed: &[Vec<f64>]) -> Result<f64, String> {
+    let mut counts = Vec::with_capacity(observed.len());
+    for row in observed {
+        let mut count_row = Vec::with_capacity(row.len());
+        for cell in row {
+            if *cell < 0. || cell.fract() != 0. {
+                return Err(String::from("the exact test needs whole number counts"));
+            }
+            count_row.push(*cell as u64);
+        }
+        counts.push(count_row);
+    }
+
+    let rows = counts.len();
+    let columns = counts.first().map_or(0, |row| row.len());
+    if rows < 2 || columns < 2 {
+        return Err(String::from("the exact test needs at least 2 rows and 2 columns"));
+    }
+
+    let row_totals: Vec<u64> = counts.iter().map(|row| row.iter().sum()).collect();
+    let column_totals: Vec<u64> = (0..columns).map(|j| counts.iter().map(|row| row[j]).sum()).collect();
+    let grand_total: u64 = row_totals.iter().sum();
+
+    let mut enumeration = Enumeration {
+        row_totals,
+        column_remaining: column_totals,
+        // no count can be more than the grand total
+        ln_factorials: (0..grand_total.min(MAX_STEPS as u64)+1).map(ln_factorial).collect(),
+        ln_constant: 0.,
+        ln_observed: 0.,
+        p_value: 0.,
+        steps: 0,
+    };
+    enumeration.ln_constant = enumeration.row_totals.iter().map(|r| enumeration.ln_factorial(*r)).sum::<f64>()
+        + enumeration.column_remaining.iter().map(|c| enumeration.ln_factorial(*c)).sum::<f64>()
+        - enumeration.ln_factorial(grand_total);
+    let ln_observed_cells: f64 = counts.iter().flatten().map(|n| enumeration.ln_factorial(*n)).sum();
+    enumeration.ln_observed = enumeration.ln_constant - ln_observed_cells;
+
+    let first_row_total = enumeration.row_totals[0];
+    enumeration.fill(0, 0, first_row_total, 0.)?;
+
+    Ok(enumeration.p_value.min(1.))
+}
+
+/// state while going through every table with the observed totals
+struct Enumeration {
+    row_totals: Vec<u64>,
+    /// what's left of each column total for the rows not filled in yet
+    column_remaining: Vec<u64>,
+    /// ln(n!) for each n up to the grand total (or `MAX_STEPS` if that's less)
+    ln_factorials: Vec<f64>,
+    /// ln(∏ row totals! ∏ column totals! / grand total!), shared by every table
+    ln_constant: f64,
+    /// ln of the observed table's probability
+    ln_observed: f64,
+    p_value: f64,
+    /// how many cells have been filled in so far
+    steps: usize,
+}
+
+impl Enumeration {
+    fn ln_factorial(&self, n: u64) -> f64 {
+        match self.ln_factorials.get(n as usize) {
+            Some(ln_factorial) => *ln_factorial,
+            None => ln_factorial(n),
+        }
+    }
+
+    /// fills in the cell at (row, column) with every possible count, recursing onto the next cell.
+    /// `ln_cells` is the sum of ln(n!) over the cells filled in so far
+    fn fill(&mut self, row: usize, column: usize, row_remaining: u64, ln_cells: f64) -> Result<(), String> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Err(String::from("the table is too large for the exact test"));
+        }
+
+        let rows = self.row_totals.len();
+        let columns = self.column_remaining.len();
+
+        // the last row is whatever is left of each column
+        if row == rows-1 {
+            let ln_cells = ln_cells + self.column_remaining.iter().map(|c| self.ln_factorial(*c)).sum::<f64>();
+            self.add_table(ln_cells);
+            return Ok(());
+        }
+
+        // the last cell in a row is whatever is left of the row
+        if column == columns-1 {
+            if row_remaining > self.column_remaining[column] {
+                return Ok(());
+            }
+            self.column_remaining[column] -= row_remaining;
+            let next_row_total = self.row_totals[row+1];
+            let result = self.fill(row+1, 0, next_row_total, ln_cells + self.ln_factorial(row_remaining));
+            self.column_remaining[column] += row_remaining;
+            return result;
+        }
+
+        for count in 0..row_remaining.min(self.column_remaining[column])+1 {
+            self.column_remaining[column] -= count;
+            let result = self.fill(row, column+1, row_remaining - count, ln_cells + self.ln_factorial(count));
+            self.column_remaining[column] += count;
+            result?;
+        }
+
+        Ok(())
+    }
+
+    fn add_table(&mut self, ln_cells: f64) {
+        let ln_probability = self.ln_constant - ln_cells;
+        if ln_probability <= self.ln_observed + TOLERANCE {
+            self.p_value += ln_probability.exp();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_by_two_tables() {
+        let p = fisher_exact(&[vec![8., 2.], vec![1., 5.]]).unwrap();
+        assert!((p - 0.034_965_034_965_035).abs() < 1e-12);
+
+        // the most likely table can't be rejected
+        let p = fisher_exact(&[vec![3., 3.], vec![3., 3.]]).unwrap();
+        assert!((p - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn freeman_halton_tables() {
+        let p = fisher_exact(&[vec![3., 0., 1.], vec![0., 4., 1.]]).unwrap();
+        assert!((p - 0.023_809_523_809_523_808).abs() < 1e-12);
+    }
+
+    #[test]
+    fn non_count_tables_are_errors() {
+        assert!(fisher_exact(&[vec![1.5, 2.], vec![3., 4.]]).is_err());
+        assert!(fisher_exact(&[vec![1., 2.]]).is_err());
+    }
+
+    #[test]
+    fn large_tables_give_up() {
+        // even small counts have too many tables to go through once there are enough cells
+        let table: Vec<Vec<f64>> = (0..4).map(|i| (0..8).map(|j| ((i + j) % 3 + 1) as f64).collect()).collect();
+        assert_eq!(fisher_exact(&table), Err(String::from("the table is too large for the exact test")));
+    }
+}
@@ -0,0 +1,71 @@
+//! splitting text into the characters a user sees (grapheme clusters) and working out
+//! how many terminal columns they take up, so labels like "λ²", "é" and "男性" can be
+//! edited and lined up in tables. segmentation and widths follow unicode's standards
+//! (through `unicode-segmentation` and `unicode-width`)
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// the text split into the characters a user sees, each with any marks joined onto it
+pub fn graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+/// how many graphemes are in the text
+pub fn count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// how many terminal columns the text takes up
+pub fn width(text: &str) -> usize {
+    text.graphemes(true)
+        // control characters (like a line break in a pasted label) don't take up a column
+        .filter(|grapheme| !grapheme.chars().all(char::is_control))
+        .map(|grapheme| grapheme.width())
+        .sum()
+}
+
+/// the byte index of the start of grapheme `index` of the text (or its end)
+pub fn byte_index(text: &str, index: usize) -> usize {
+    text.grapheme_indices(true).nth(index).map_or(text.len(), |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphemes_join_marks_and_emoji() {
+        assert_eq!(graphemes("λ²"), vec!["λ", "²"]);
+        // e followed by a combining acute accent
+        assert_eq!(graphemes("cafe\u{301}"), vec!["c", "a", "f", "e\u{301}"]);
+        assert_eq!(graphemes("👍🏽!"), vec!["👍🏽", "!"]);
+        assert_eq!(graphemes("🇬🇧🇫🇷"), vec!["🇬🇧", "🇫🇷"]);
+        assert_eq!(graphemes("👩\u{200D}💻"), vec!["👩\u{200D}💻"]);
+        assert_eq!(graphemes("👨\u{200D}👩\u{200D}👧\u{200D}👦x"), vec!["👨\u{200D}👩\u{200D}👧\u{200D}👦", "x"]);
+        assert_eq!(graphemes("a\r\nb"), vec!["a", "\r\n", "b"]);
+        // hindi, with a vowel sign joined onto its consonant
+        assert_eq!(graphemes("हिंदी"), vec!["हिं", "दी"]);
+        assert!(graphemes("").is_empty());
+    }
+
+    #[test]
+    fn terminal_widths() {
+        assert_eq!(width("Observed"), 8);
+        assert_eq!(width("cafe\u{301}"), 4);
+        assert_eq!(width("café"), 4);
+        assert_eq!(width("男性"), 4);
+        assert_eq!(width("한국어"), 6);
+        assert_eq!(width("ｘ²"), 3);
+        assert_eq!(width("👩\u{200D}💻"), 2);
+        assert_eq!(width("🇬🇧"), 2);
+        assert_eq!(width("a\r\nb"), 2);
+    }
+
+    #[test]
+    fn byte_indices_of_graphemes() {
+        assert_eq!(byte_index("男性", 1), 3);
+        assert_eq!(byte_index("e\u{301}x", 1), 3);
+        assert_eq!(byte_index("ab", 5), 2);
+    }
+}
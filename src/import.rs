@@ -0,0 +1,294 @@
+//! loading tables from CSV/TSV files
+//!
+//! the delimiter is detected from the file (tab, comma, semicolon, or else whitespace, with a
+//! single column read as comma separated). cells can be quoted like "Smith, J", with "" for a
+//! quote inside them, and quoted cells can run over more than one line.
+//! the first row is taken as column labels if any of its cells after the first aren't numbers
+//! or its first cell is blank, and the first column is taken as row labels if any of its cells below the header aren't numbers
+
+use std::fs;
+
+/// a table of cells read from a file, with any labels that were detected
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTable {
+    /// label above the row labels, if there were both column and row labels
+    pub corner_label: Option<String>,
+    pub column_labels: Option<Vec<String>>,
+    pub row_labels: Option<Vec<String>>,
+    /// the rest of the cells, left as text so they can be reviewed in the editor
+    pub cells: Vec<Vec<String>>,
+}
+
+/// series of values read from a table, with the label of each class if there were any
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub labels: Option<Vec<String>>,
+    pub values: Vec<Vec<String>>,
+}
+
+impl ImportedTable {
+    pub fn rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    /// reads the table as `count` series of values, such as observed and expected,
+    /// returning any class labels and each series.
+    /// series can be laid out as rows (labelled by the column labels) or as
+    /// columns (labelled by the row labels), with an extra row/column of numeric labels
+    /// (such as the values of X) read as the class labels
+    pub fn series(&self, count: usize) -> Result<Series, String> {
+        let columns = |cells: &[Vec<String>], from: usize| -> Vec<Vec<String>> {
+            (from..from+count).map(|j| cells.iter().map(|row| row[j].clone()).collect()).collect()
+        };
+
+        if self.rows() == count {
+            Ok(Series { labels: self.column_labels.clone(), values: self.cells.clone() })
+        } else if self.columns() == count {
+            Ok(Series { labels: self.row_labels.clone(), values: columns(&self.cells, 0) })
+        } else if self.rows() == count+1 && self.column_labels.is_none() {
+            Ok(Series { labels: Some(self.cells[0].clone()), values: self.cells[1..].to_vec() })
+        } else if self.columns() == count+1 && self.row_labels.is_none() {
+            let labels = self.cells.iter().map(|row| row[0].clone()).collect();
+            Ok(Series { labels: Some(labels), values: columns(&self.cells, 1) })
+        } else {
+            Err(format!(
+                "expected {count} row{s} or column{s} of values, found {} rows and {} columns",
+                self.rows(),
+                self.columns(),
+                s = if count == 1 {""} else {"s"}
+            ))
+        }
+    }
+}
+
+/// reads and parses a CSV/TSV file
+pub fn read_table(path: &str) -> Result<ImportedTable, String> {
+    let contents = fs::read_to_string(path).map_err(|error| format!("could not read '{path}': {error}"))?;
+    parse_table(&contents)
+}
+
+/// parses CSV/TSV text into a table, detecting the delimiter and any labels
+pub fn parse_table(contents: &str) -> Result<ImportedTable, String> {
+    let records = records(contents);
+    if records.is_empty() {
+        return Err(String::from("the file has no rows"));
+    }
+
+    let delimiter = detect_delimiter(&records);
+    let mut rows: Vec<Vec<String>> = records.iter().map(|record| split_record(record, delimiter)).collect();
+
+    let columns = rows[0].len();
+    if let Some(i) = rows.iter().position(|row| row.len() != columns) {
+        return Err(format!("row {} has {} cells but row 1 has {columns}", i+1, rows[i].len()));
+    }
+
+    // a blank corner cell also marks a header, so numeric column labels (like 0, 1, 2) can be given
+    let has_header = rows[0].iter().skip(1).any(|cell| !is_number(cell))
+        || (rows[0][0].is_empty() && columns > 1)
+        || (columns == 1 && !is_number(&rows[0][0]) && rows.len() > 1);
+    let header = if has_header {Some(rows.remove(0))} else {None};
+    if rows.is_empty() {
+        return Err(String::from("the file only has a header row"));
+    }
+
+    let has_row_labels = columns > 1 && rows.iter().any(|row| !is_number(&row[0]));
+    let row_labels = if has_row_labels {
+        Some(rows.iter_mut().map(|row| row.remove(0)).collect())
+    } else {
+        None
+    };
+
+    let (corner_label, column_labels) = match header {
+        Some(mut header) if has_row_labels => {
+            let corner = header.remove(0);
+            (Some(corner), Some(header))
+        },
+        Some(header) => (None, Some(header)),
+        None => (None, None),
+    };
+
+    Ok(ImportedTable { corner_label, column_labels, row_labels, cells: rows })
+}
+
+/// splits text into rows of cells like a file, without looking for labels or needing
+/// every row to be the same length, such as rows pasted in from a spreadsheet
+pub fn split_rows(text: &str) -> Vec<Vec<String>> {
+    let records = records(text);
+    let delimiter = detect_delimiter(&records);
+    records.iter().map(|record| split_record(record, delimiter)).collect()
+}
+
+/// the rows of the text: its lines, except that a line break inside quotes stays in the cell.
+/// blank lines are left out
+fn records(text: &str) -> Vec<&str> {
+    let mut records = Vec::new();
+    let mut start = 0;
+    // a "" inside quotes closes and reopens them, so leaves them open
+    let mut in_quotes = false;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            '\n' if !in_quotes => {
+                records.push(&text[start..i]);
+                start = i + 1;
+            },
+            _ => {}
+        }
+    }
+    records.push(&text[start..]);
+
+    records.into_iter()
+        .map(|record| record.strip_suffix('\r').unwrap_or(record))
+        .filter(|record| !record.trim().is_empty())
+        .collect()
+}
+
+/// whether the record has `delimiter` in it outside of any quotes
+fn has_delimiter(record: &str, delimiter: char) -> bool {
+    let mut in_quotes = false;
+    record.chars().any(|ch| {
+        if ch == '"' {
+            in_quotes = !in_quotes;
+        }
+        ch == delimiter && !in_quotes
+    })
+}
+
+/// the delimiter every record has, or `None` to split them on whitespace. records that
+/// can't all be split on whitespace are a single column, which is read as comma separated
+fn detect_delimiter(records: &[&str]) -> Option<char> {
+    let delimiter = [ '\t', ',', ';' ].into_iter().find(|delimiter| records.iter().all(|record| has_delimiter(record, *delimiter)));
+    if delimiter.is_none() && records.iter().any(|record| split_record(record, None).len() < 2) {
+        return Some(',');
+    }
+
+    delimiter
+}
+
+/// splits a record on the delimiter (or runs of whitespace), trimming the cells.
+/// a quoted cell is kept as it is inside the quotes, with "" read as a quote
+fn split_record(record: &str, delimiter: Option<char>) -> Vec<String> {
+    let is_delimiter = |ch: char| match delimiter {
+        Some(delimiter) => ch == delimiter,
+        None => ch.is_whitespace(),
+    };
+    let finish = |cell: &str, quoted: bool| if quoted {cell.to_string()} else {cell.trim().to_string()};
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    // whether the cell started with a quote, and whether it's still open
+    let (mut quoted, mut in_quotes) = (false, false);
+    let mut chars = record.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            if ch != '"' {
+                cell.push(ch);
+            } else if chars.peek() == Some(&'"') {
+                cell.push('"');
+                chars.next();
+            } else {
+                in_quotes = false;
+            }
+        } else if ch == '"' && !quoted && cell.trim().is_empty() {
+            cell.clear();
+            (quoted, in_quotes) = (true, true);
+        } else if is_delimiter(ch) {
+            // a run of whitespace only separates one pair of cells
+            if delimiter.is_some() || quoted || !cell.is_empty() {
+                cells.push(finish(&cell, quoted));
+            }
+            cell.clear();
+            quoted = false;
+        } else if !(quoted && ch.is_whitespace()) {
+            cell.push(ch);
+        }
+    }
+    if delimiter.is_some() || quoted || !cell.is_empty() {
+        cells.push(finish(&cell, quoted));
+    }
+
+    cells
+}
+
+/// empty cells count as numbers so a missing value doesn't turn a row into labels
+fn is_number(cell: &str) -> bool {
+    cell.is_empty() || cell.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_headers_and_row_labels() {
+        let table = parse_table("eye colour,blue,brown\nmale,10,20\nfemale,30,40\n").unwrap();
+        assert_eq!(table.corner_label, Some(String::from("eye colour")));
+        assert_eq!(table.column_labels, Some(vec![String::from("blue"), String::from("brown")]));
+        assert_eq!(table.row_labels, Some(vec![String::from("male"), String::from("female")]));
+        assert_eq!(table.cells, vec![vec!["10", "20"], vec!["30", "40"]]);
+    }
+
+    #[test]
+    fn plain_numbers_have_no_labels() {
+        let table = parse_table("1\t2\t3\n4\t5\t6").unwrap();
+        assert_eq!(table.column_labels, None);
+        assert_eq!(table.row_labels, None);
+        assert_eq!((table.rows(), table.columns()), (2, 3));
+    }
+
+    #[test]
+    fn series_as_rows_or_columns() {
+        let rows = parse_table(",0,1,2\nObserved,5,10,15").unwrap();
+        let Series { labels, values: series } = rows.series(1).unwrap();
+        assert_eq!(labels, Some(vec![String::from("0"), String::from("1"), String::from("2")]));
+        assert_eq!(series, vec![vec!["5", "10", "15"]]);
+
+        let numeric_labels = parse_table("0 5\n1 10\n2 15").unwrap();
+        let Series { labels, values: series } = numeric_labels.series(1).unwrap();
+        assert_eq!(labels, Some(vec![String::from("0"), String::from("1"), String::from("2")]));
+        assert_eq!(series, vec![vec!["5", "10", "15"]]);
+
+        let columns = parse_table("class,O,E\nA,10,12\nB,14,12\nC,12,12").unwrap();
+        let Series { labels, values: series } = columns.series(2).unwrap();
+        assert_eq!(labels, Some(vec![String::from("A"), String::from("B"), String::from("C")]));
+        assert_eq!(series, vec![vec!["10", "14", "12"], vec!["12", "12", "12"]]);
+
+        assert!(parse_table("1,2,3,4\n5,6,7,8\n9,10,11,12\n13,14,15,16").unwrap().series(2).is_err());
+    }
+
+    #[test]
+    fn quoted_cells() {
+        let table = parse_table("name,before,after\n\"Smith, J\",1,2\n\"say \"\"hi\"\"\" , 3,4\n").unwrap();
+        assert_eq!(table.row_labels, Some(vec![String::from("Smith, J"), String::from("say \"hi\"")]));
+        assert_eq!(table.cells, vec![vec!["1", "2"], vec!["3", "4"]]);
+
+        // a line break inside quotes stays in the cell
+        let table = parse_table("\t\"first\nline\"\tsecond\nrow\t1\t2").unwrap();
+        assert_eq!(table.column_labels, Some(vec![String::from("first\nline"), String::from("second")]));
+
+        assert_eq!(split_rows("\"a b\" c"), vec![vec!["a b", "c"]]);
+    }
+
+    #[test]
+    fn single_columns() {
+        let table = parse_table("count\n5\n10\n15\n").unwrap();
+        assert_eq!(table.column_labels, Some(vec![String::from("count")]));
+        assert_eq!(table.cells, vec![vec!["5"], vec!["10"], vec!["15"]]);
+
+        // labels with spaces or quoted commas in them stay whole
+        let table = parse_table("eye colour\n12\n\"3,000\"").unwrap();
+        assert_eq!(table.column_labels, Some(vec![String::from("eye colour")]));
+        assert_eq!(table.cells, vec![vec!["12"], vec!["3,000"]]);
+    }
+
+    #[test]
+    fn pasted_rows() {
+        assert_eq!(split_rows("12 15 9 22"), vec![vec!["12", "15", "9", "22"]]);
+        assert_eq!(split_rows("1\t\t3\r\n4\t5\n"), vec![vec!["1", "", "3"], vec!["4", "5"]]);
+        assert!(split_rows("\n").is_empty());
+    }
+}
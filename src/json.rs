@@ -0,0 +1,328 @@
+//! JSON output of test results, so reports and dashboards can read them directly
+//!
+//! # Example
+//! ```
+//! use chi_squared::{json, stats, Significance};
+//!
+//! let result = stats::observed_expected(&[10., 12., 8.], &[10., 10., 10.])?;
+//! let output = json::goodness_of_fit(&result, Significance::new(5.)).to_string();
+//! assert!(output.starts_with(r#"{"mode":"oe""#));
+//! # Ok::<(), String>(())
+//! ```
+
+use std::fmt;
+
+use crate::stats::{ContingencyTest, GoodnessOfFit, Model};
+use crate::Significance;
+
+/// a JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// fields are kept in the order they were added
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// an object from (name, value) pairs
+    pub fn object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+        Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Json {
+        Json::Bool(value)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Json {
+        Json::Number(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Json {
+        Json::Number(value as f64)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Json {
+        Json::String(value)
+    }
+}
+
+impl<T: Clone + Into<Json>> From<&[T]> for Json {
+    fn from(values: &[T]) -> Json {
+        Json::Array(values.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Json {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            // JSON has no infinity or NaN
+            Json::Number(value) if !value.is_finite() => write!(f, "null"),
+            Json::Number(value) => write!(f, "{value}"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for ch in value.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            ch if (ch as u32) < 0x20 => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => write!(f, "{ch}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// the mode name and parameters of the model a goodness of fit test used,
+/// with whether each was estimated from the data
+fn model(model: &Model, estimated: &[&str]) -> (&'static str, Json) {
+    let estimated = |parameter| Json::from(estimated.contains(&parameter));
+    match *model {
+        Model::Given => ("oe", Json::object([])),
+        Model::Binomial { n, p } => ("binomial", Json::object([
+            ("n", n.into()),
+            ("p", p.into()),
+            ("p_estimated", estimated("p")),
+        ])),
+        Model::Poisson { mean } => ("poisson", Json::object([
+            ("lambda", mean.into()),
+            ("lambda_estimated", estimated("lambda")),
+        ])),
+        Model::Geometric { p } => ("geometric", Json::object([
+            ("p", p.into()),
+            ("p_estimated", estimated("p")),
+        ])),
+        Model::NegativeBinomial { r, p } => ("negative_binomial", Json::object([
+            ("r", r.into()),
+            ("r_estimated", estimated("r")),
+            ("p", p.into()),
+            ("p_estimated", estimated("p")),
+        ])),
+        Model::Normal { mean, sd } => ("normal", Json::object([
+            ("mean", mean.into()),
+            ("mean_estimated", estimated("mean")),
+            ("sd", sd.into()),
+            ("sd_estimated", estimated("sd")),
+        ])),
+        Model::Uniform { a, b } => ("uniform", Json::object([
+            ("a", a.into()),
+            ("a_estimated", false.into()),
+            ("b", b.into()),
+            ("b_estimated", false.into()),
+        ])),
+        Model::Exponential { rate } => ("exponential", Json::object([
+            ("lambda", rate.into()),
+            ("lambda_estimated", estimated("lambda")),
+        ])),
+    }
+}
+
+/// the first and last original class (counting from 0) in each class, if they were grouped
+fn grouping(result: &GoodnessOfFit) -> Json {
+    match &result.grouping {
+        Some(grouping) => Json::Array(
+            grouping.groups.iter().map(|(first, last)| Json::from(vec![*first, *last])).collect()
+        ),
+        None => Json::Null,
+    }
+}
+
+/// the statistic, degrees of freedom, p-value and verdict shared by every test.
+/// the p-value and verdict are for the yates corrected statistic if there is one.
+/// if the statistic or p-value isn't finite there's no verdict, just an error
+fn test_fields(
+    statistic: f64,
+    yates_statistic: Option<f64>,
+    df: usize,
+    p_value: f64,
+    critical_value: f64,
+    significance: Significance
+) -> [(&'static str, Json); 8] {
+    let decision = significance.rejects(yates_statistic.unwrap_or(statistic), p_value);
+    [
+        ("statistic", statistic.into()),
+        ("yates_statistic", yates_statistic.map_or(Json::Null, Json::from)),
+        ("df", df.into()),
+        ("p_value", p_value.into()),
+        ("significance", significance.proportion().into()),
+        ("critical_value", critical_value.into()),
+        ("reject_h0", decision.as_ref().map_or(Json::Null, |reject| (*reject).into())),
+        ("error", decision.err().map_or(Json::Null, Json::from)),
+    ]
+}
+
+/// the full result of a goodness of fit test
+pub fn goodness_of_fit(result: &GoodnessOfFit, significance: Significance) -> Json {
+    let (mode, parameters) = model(&result.model, &result.estimated);
+
+    let mut fields = vec![
+        (String::from("mode"), Json::from(mode)),
+        (String::from("parameters"), parameters),
+        (String::from("estimated_parameters"), result.estimated_parameters().into()),
+        (String::from("class_labels"), result.class_labels.as_slice().into()),
+        (String::from("grouping"), grouping(result)),
+        (String::from("observed"), result.observed.as_slice().into()),
+        (String::from("expected"), result.expected.as_slice().into()),
+        (String::from("contributions"), result.contributions().into()),
+    ];
+    fields.extend(
+        test_fields(
+            result.statistic,
+            result.yates_statistic,
+            result.df,
+            result.p_value(),
+            result.critical_value(significance.proportion()),
+            significance
+        ).into_iter().map(|(name, value)| (name.to_string(), value))
+    );
+    fields.push((String::from("low_expecteds"), result.has_low_expecteds().into()));
+
+    Json::Object(fields)
+}
+
+/// the full result of a contingency table test
+pub fn contingency_table(result: &ContingencyTest, significance: Significance) -> Json {
+    let mut fields = vec![
+        (String::from("mode"), Json::from("contingency")),
+        (String::from("row_labels"), result.row_labels.as_slice().into()),
+        (String::from("column_labels"), result.column_labels.as_slice().into()),
+        (String::from("observed"), result.observed.clone().into()),
+        (String::from("expected"), result.expected.clone().into()),
+        (String::from("contributions"), result.contributions().into()),
+    ];
+    fields.extend(
+        test_fields(
+            result.statistic,
+            result.yates_statistic,
+            result.df,
+            result.p_value(),
+            result.critical_value(significance.proportion()),
+            significance
+        ).into_iter().map(|(name, value)| (name.to_string(), value))
+    );
+    fields.push((String::from("low_expecteds"), result.has_low_expecteds().into()));
+    fields.push((String::from("exact_p_value"), result.exact_p_value.map_or(Json::Null, Json::from)));
+    fields.push((String::from("exact_error"), result.exact_error.as_deref().map_or(Json::Null, Json::from)));
+
+    Json::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats;
+
+    #[test]
+    fn values_serialize() {
+        let value = Json::object([
+            ("text", "a \"quoted\"\nline".into()),
+            ("numbers", vec![1., 2.5, f64::NAN].into()),
+            ("flag", true.into()),
+            ("nothing", Json::Null),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"text":"a \"quoted\"\nline","numbers":[1,2.5,null],"flag":true,"nothing":null}"#
+        );
+    }
+
+    #[test]
+    fn estimated_parameters() {
+        let labels: Vec<String> = ["0-10", "10-20", "20-30", "30-40"].iter().map(|l| l.to_string()).collect();
+        let observed = [20., 30., 30., 20.];
+        let result = stats::normal(
+            stats::NormalMean::Mean(20.),
+            stats::NormalSd::Estimate,
+            &labels,
+            &observed,
+            stats::MinimumExpected::default()
+        ).unwrap();
+        let output = goodness_of_fit(&result, Significance::new(5.)).to_string();
+        assert!(output.contains(r#""mean":20,"mean_estimated":false"#));
+        assert!(output.contains(r#""sd_estimated":true"#));
+        assert!(output.contains(r#""estimated_parameters":1"#));
+
+        let observed = [70., 38., 22., 14., 9., 7., 5., 10.];
+        let result = stats::negative_binomial(
+            stats::NegativeBinomialR::R(1.),
+            stats::NegativeBinomialP::Estimate,
+            &observed,
+            stats::MinimumExpected::default()
+        ).unwrap();
+        let output = goodness_of_fit(&result, Significance::new(5.)).to_string();
+        assert!(output.contains(r#""r":1,"r_estimated":false"#));
+        assert!(output.contains(r#""p_estimated":true"#));
+    }
+
+    #[test]
+    fn contingency_table_fields() {
+        let result = stats::contingency_table(&[vec![10., 20.], vec![30., 40.]]).unwrap();
+        let output = contingency_table(&result, Significance::new(5.)).to_string();
+        assert!(output.contains(r#""observed":[[10,20],[30,40]]"#));
+        assert!(output.contains(r#""expected":[[12,18],[28,42]]"#));
+        assert!(output.contains(r#""df":1"#));
+        assert!(output.contains(r#""yates_statistic":0.4464285714285714"#));
+        assert!(output.contains(r#""reject_h0":false,"error":null"#));
+        assert!(output.contains(r#""exact_p_value":null,"exact_error":null"#));
+    }
+
+    #[test]
+    fn undecided_tests_carry_an_error() {
+        let fields = test_fields(f64::INFINITY, None, 1, f64::NAN, 3.841, Significance::new(5.));
+        let output = Json::object(fields).to_string();
+        assert!(output.contains(r#""reject_h0":null,"error":"the test can't be decided"#));
+    }
+}
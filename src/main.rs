@@ -1,11 +1,29 @@
-use chi_squared::Mode;
+use chi_squared::{Mode, Significance};
 
 fn main() {
+    // run straight from the arguments if there are any, for scripting
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(error) = chi_squared::cli::run(&args) {
+            eprintln!("error: {error}\n\nrun 'chi_squared help' to see how to use it");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // code in src/lib.rs
-    match Mode::get_mode() {
-        Mode::OE => chi_squared::observed_expected(),
-        Mode::Binomial => chi_squared::binomial(),
-        Mode::Poisson => chi_squared::poission(),
-        Mode::ContingencyTable => chi_squared::contingency_table()
+    let mode = Mode::get_mode();
+    let significance = Significance::get_significance();
+
+    match mode {
+        Mode::OE => chi_squared::observed_expected(significance),
+        Mode::Binomial => chi_squared::binomial(significance),
+        Mode::Poisson => chi_squared::poission(significance),
+        Mode::ContingencyTable => chi_squared::contingency_table(significance),
+        Mode::Geometric => chi_squared::geometric(significance),
+        Mode::Normal => chi_squared::normal(significance),
+        Mode::Uniform => chi_squared::uniform(significance),
+        Mode::Exponential => chi_squared::exponential(significance),
+        Mode::NegativeBinomial => chi_squared::negative_binomial(significance)
     }
 }   
@@ -0,0 +1,1022 @@
+//! the interactive screen for each mode, which read a table from the
+//! user then print the result of the matching test in [`crate::stats`]
+
+use crossterm::{terminal, ExecutableCommand};
+use crossterm::event::KeyCode;
+use costottorama::{text, back, style};
+use std::io;
+
+use crate::distribution::ChiSquared;
+use crate::stats::{
+    self, BinomialP, ExponentialRate, Frequencies, GeometricP, MinimumExpected, NegativeBinomialP,
+    NegativeBinomialR, NormalMean, NormalSd, PoissonMean, UniformRange
+};
+use crate::import::{self, ImportedTable};
+use crate::tui::{
+    display_title, range_input, clearing_text_input, yes_no_input, get_key_pressed,
+    print_table, edit_table, parse_cells, Overflow, Resizing, parse_column_labels, both, TableError, TableDisplay
+};
+use crate::Significance;
+
+/// prints the hypotheses and statistic with its degrees of freedom, p-value
+/// and critical value, then whether the null hypothesis is rejected (or why it
+/// can't be decided). if yates' correction was applied, the corrected statistic decides the test
+fn print_test_result(
+    gof: f64,
+    yates_gof: Option<f64>,
+    df: usize,
+    significance: Significance,
+    null_hypothesis: &str,
+    alternative_hypothesis: &str,
+) {
+    let distribution = ChiSquared::new(df);
+    let test_statistic = yates_gof.unwrap_or(gof);
+    let p_value = distribution.sf(test_statistic);
+    let critical_value = distribution.critical_value(significance.proportion());
+
+    println!("\nH0: {null_hypothesis}");
+    println!("H1: {alternative_hypothesis}");
+
+    if let Some(yates_gof) = yates_gof {
+        println!("\nX² (uncorrected) = {}{}{}", text::GREEN, gof, text::RESET);
+        println!("X² (with Yates' correction) = {}{}{}", text::GREEN, yates_gof, text::RESET);
+    } else {
+        println!("\nX² = {}{}{}", text::GREEN, gof, text::RESET);
+    }
+    println!("degrees of freedom = {}{}{}", text::GREEN, df, text::RESET);
+    println!("p-value = {}{}{}", text::GREEN, p_value, text::RESET);
+    println!("critical value at {significance} = {}{}{}", text::GREEN, critical_value, text::RESET);
+
+    let reject = match significance.rejects(test_statistic, p_value) {
+        Ok(reject) => reject,
+        Err(error) => {
+            println!("\n{}{error}{}", text::RED, text::RESET);
+            return;
+        }
+    };
+    if reject {
+        println!(
+            "\n{}X² > {critical_value:.3}, so reject H0 at the {significance} level{}",
+            text::RED,
+            text::RESET
+        );
+    } else {
+        println!(
+            "\n{}X² <= {critical_value:.3}, so do not reject H0 at the {significance} level{}",
+            text::GREEN,
+            text::RESET
+        );
+    }
+}
+
+/// asks for a CSV/TSV file to fill the table from, asking again with the problem
+/// if it can't be read into the table `read` wants.
+/// returns `None` if left blank so the table can be typed in instead
+fn import_input<T>(read: impl Fn(&ImportedTable) -> Result<T, String>) -> Option<T> {
+    let mut error: Option<String> = None;
+    loop {
+        if let Some(error) = &error {
+            println!("{}{error}{}", text::RED, text::RESET);
+        }
+        let path = clearing_text_input("enter a CSV/TSV file to load (leave blank to type the table in):");
+        if error.is_some() {
+            io::stdout().execute(crossterm::cursor::MoveUp(1)).unwrap();
+            io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+        }
+
+        if path.is_empty() {
+            return None;
+        }
+        match import::read_table(&path).and_then(|table| read(&table)) {
+            Ok(imported) => return Some(imported),
+            Err(e) => error = Some(e)
+        }
+    }
+}
+
+/// asks for text until `parse` accepts it, showing the problem with anything it doesn't
+fn parsed_text_input<T>(prompt: &str, parse: impl Fn(&str) -> Result<T, String>) -> T {
+    let mut error: Option<String> = None;
+    loop {
+        if let Some(error) = &error {
+            println!("{}{error}{}", text::RED, text::RESET);
+        }
+        let input = clearing_text_input(prompt);
+        if error.is_some() {
+            io::stdout().execute(crossterm::cursor::MoveUp(1)).unwrap();
+            io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+        }
+
+        match parse(&input) {
+            Ok(parsed) => return parsed,
+            Err(e) => error = Some(e)
+        }
+    }
+}
+
+/// asks whether the observed frequencies can be weighted rather than whole counts, clearing it once answered
+fn frequencies_input() -> Frequencies {
+    if yes_no_input("are the observed frequencies weighted, so not always whole numbers? (y/N)", false) {
+        Frequencies::Weighted
+    } else {
+        Frequencies::Counts
+    }
+}
+
+/// asks for the rule classes are grouped until their expected frequencies meet, clearing it once answered
+fn minimum_expected_input() -> MinimumExpected {
+    println!(" [1] every expected frequency at least 5\n [2] every expected frequency at least another number\n [3] Cochran's rule (all at least 1, at most 20% below 5)\n");
+    let choice = range_input("choose how to group classes with low expected frequencies:", 1, 3);
+    io::stdout().execute(crossterm::cursor::MoveUp(4)).unwrap();
+    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+    match choice {
+        2 => MinimumExpected::AtLeast(parsed_text_input("enter the minimum expected frequency:", |text| {
+            match text.trim().parse::<f64>() {
+                Ok(minimum) if minimum > 0. && minimum.is_finite() => Ok(minimum),
+                Ok(_) => Err(String::from("the minimum must be a positive number")),
+                Err(_) => Err(format!("'{}' is not a number", text.trim())),
+            }
+        })),
+        3 => MinimumExpected::Cochran,
+        _ => MinimumExpected::default()
+    }
+}
+
+/// an observed frequency for a table: whole counts as they are, and
+/// weighted frequencies to at most 4 decimal places
+fn format_frequency(frequency: f64) -> String {
+    ((frequency * 1e4).round() / 1e4).to_string()
+}
+
+/// asks how the expected frequencies will be found,
+/// returning `None` if they'll be typed into the table
+fn expected_frequencies_input() -> Option<stats::ExpectedFrequencies> {
+    println!(" [1] enter the expected frequencies\n [2] every class equally likely\n [3] in a ratio, like 9:3:3:1\n [4] as proportions, like 0.5,0.25,0.25\n");
+    let choice = range_input("choose how to find the expected frequencies:", 1, 4);
+    io::stdout().execute(crossterm::cursor::MoveUp(5)).unwrap();
+    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+    match choice {
+        2 => Some(stats::ExpectedFrequencies::Uniform),
+        3 => Some(stats::ExpectedFrequencies::Ratio(parsed_text_input("enter the ratio:", stats::parse_ratio))),
+        4 => Some(stats::ExpectedFrequencies::Proportions(parsed_text_input("enter the proportions:", stats::parse_proportions))),
+        _ => None
+    }
+}
+
+pub fn observed_expected(significance: Significance) {
+    display_title("O vs E");
+
+    let frequencies = frequencies_input();
+    // expecteds worked out from the observed total only need the observed row
+    let expected_frequencies = expected_frequencies_input();
+    let (series_count, classes) = match &expected_frequencies {
+        None => (2, None),
+        Some(stats::ExpectedFrequencies::Ratio(parts) | stats::ExpectedFrequencies::Proportions(parts)) => (1, Some(parts.len())),
+        Some(_) => (1, None),
+    };
+
+    let imported = import_input(|table| {
+        let series = table.series(series_count)?;
+        match classes {
+            Some(classes) if series.values[0].len() != classes => Err(format!("expected {classes} observed frequencies, found {}", series.values[0].len())),
+            _ => Ok(series)
+        }
+    });
+    let columns = match (&imported, classes) {
+        (Some(series), _) => series.values[0].len(),
+        (None, Some(classes)) => classes,
+        (None, None) => range_input("enter the number of columns:", 1, u32::MAX) as usize
+    };
+    let mut table = vec![vec![String::from(""); columns]; series_count];
+    let mut column_labels = vec![String::from("---"); columns];
+    let mut row_labels = vec![String::from("type"); series_count+1];
+    row_labels[1] = String::from("Observed");
+    if series_count == 2 {
+        row_labels[2] = String::from("Expected");
+    }
+
+    if let Some(series) = imported {
+        table = series.values;
+        if let Some(labels) = series.labels {column_labels = labels}
+    }
+
+    let (expected_frequencies, mut result) = edit_table(&mut table, &mut column_labels, &mut row_labels, Resizing::COLUMNS, |table, _, _| {
+        let float_table = parse_cells(table, |i, cell| {
+            if i == 0 {
+                frequencies.parse(cell)
+            } else {
+                match cell.trim().parse::<f64>() {
+                    Ok(expected) if expected > 0. && expected.is_finite() => Ok(expected),
+                    _ => Err(format!("'{}' is not a positive expected frequency", cell.trim()))
+                }
+            }
+        })?;
+
+        let expected_frequencies = match &expected_frequencies {
+            Some(expected_frequencies) => expected_frequencies.clone(),
+            None => stats::ExpectedFrequencies::Given(float_table[1].clone())
+        };
+        match expected_frequencies.expecteds(&float_table[0]).and_then(|expected| stats::observed_expected(&float_table[0], &expected)) {
+            Ok(result) => Ok((expected_frequencies, result)),
+            Err(error) => Err(vec![TableError::whole(error)])
+        }
+    });
+
+    result.class_labels = column_labels;
+    let mut result = merge_observed_expected(result);
+    if result.df == 1 {
+        result.set_yates_correction(yes_no_input("apply Yates' continuity correction? (Y/n)", true));
+    }
+
+    print_observed_expected(&result, &expected_frequencies, significance);
+}
+
+/// while any expected frequency is below 5, shows them highlighted and offers
+/// to merge adjacent classes, either automatically or by choosing them
+fn merge_observed_expected(mut result: stats::GoodnessOfFit) -> stats::GoodnessOfFit {
+    let mut redrawn = false;
+    while result.has_low_expecteds() && result.observed.len() > 2 {
+        display_title("O vs E");
+        redrawn = true;
+        print_goodness_of_fit(&result);
+        print_low_expected_warning();
+        println!("\n [1] merge automatically\n [2] merge two adjacent columns\n [3] continue without merging\n");
+
+        match range_input("choose an option:", 1, 3) {
+            1 => result = result.merge_low_expecteds(),
+            2 => {
+                let last = result.observed.len() as u32 - 1;
+                let first = range_input(&format!("enter the first of the two columns to merge (1 to {last}):"), 1, last);
+                result = result.merge_classes(first as usize - 1);
+            },
+            _ => break
+        }
+    }
+
+    if redrawn {
+        display_title("O vs E");
+    }
+    result
+}
+
+/// prints the table and test result of an O vs E test, with hypotheses
+/// for where the expected frequencies came from
+pub fn print_observed_expected(
+    result: &stats::GoodnessOfFit,
+    expected_frequencies: &stats::ExpectedFrequencies,
+    significance: Significance
+) {
+    let list = |values: &[f64], separator: &str| values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(separator);
+    let (null_hypothesis, alternative_hypothesis) = match expected_frequencies {
+        stats::ExpectedFrequencies::Given(_) => (
+            String::from("the observed frequencies fit the expected frequencies"),
+            String::from("the observed frequencies do not fit the expected frequencies")
+        ),
+        stats::ExpectedFrequencies::Uniform => (
+            String::from("every class is equally likely"),
+            String::from("the classes are not all equally likely")
+        ),
+        stats::ExpectedFrequencies::Ratio(parts) => (
+            format!("the frequencies are in the ratio {}", list(parts, ":")),
+            format!("the frequencies are not in the ratio {}", list(parts, ":"))
+        ),
+        stats::ExpectedFrequencies::Proportions(proportions) => (
+            format!("the classes are in the proportions {}", list(proportions, ", ")),
+            format!("the classes are not in the proportions {}", list(proportions, ", "))
+        ),
+    };
+
+    print_goodness_of_fit(result);
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &null_hypothesis,
+        &alternative_hypothesis
+    );
+    if result.has_low_expecteds() {
+        print_low_expected_warning();
+    }
+}
+
+fn print_low_expected_warning() {
+    println!(
+        "\n{}highlighted expected frequencies are below 5, so the X² approximation may not be valid{}",
+        text::YELLOW,
+        text::RESET
+    );
+}
+
+/// edits the two parameters of a distribution, shown as `X ~ distribution(first: _, second: _)`
+/// with a hint after it, until `parse` accepts the text entered for each. whatever's wrong
+/// is shown under them, and kept up to date as it's fixed
+fn edit_distribution_parameters<T>(
+    distribution: &str,
+    names: [&str; 2],
+    hint: &str,
+    parse: impl Fn(&str, &str) -> Result<T, String>
+) -> T {
+    let mut pos = 0;
+    let mut first = String::from("");
+    let mut second = String::from("");
+    // problems are only shown once the user has tried to finish
+    let mut error: Option<String> = None;
+
+    let pos_style = &format!("{}{}{}",
+        back::WHITE, 
+        text::BLACK, 
+        style::BOLD,
+    );
+    let reset_pos_style = &format!("{}{}{}", back::RESET, text::RESET, style::RESET_ALL);
+
+    io::stdout().execute(crossterm::cursor::Hide).unwrap();
+    loop {
+        println!(
+            "X ~ {distribution}({}: {} {} {}, {}: {} {} {}) {}({hint}){}",
+            names[0],
+            if pos == 0 {pos_style} else {back::LIGHT_BLACK},
+            if !first.is_empty() {&first} else {" "},
+            reset_pos_style,
+            names[1],
+            if pos == 1 {pos_style} else {back::LIGHT_BLACK},
+            if !second.is_empty() {&second} else {" "},
+            reset_pos_style,
+            text::MAGENTA,
+            text::RESET
+        );
+        let mut lines = 3;
+        if let Some(error) = &error {
+            println!("\n{}{error}{}", text::RED, text::RESET);
+            lines += 2;
+        }
+        println!("\npress {}[esc]{} to finish",
+            text::LIGHT_BLUE,
+            text::RESET,
+        );
+
+        let key_pressed = get_key_pressed();
+
+        if pos == 0 {
+            if key_pressed == KeyCode::Right {pos = 1}
+            else if key_pressed == KeyCode::Backspace {
+                if first.chars().count() > 0 {first.pop();}
+            }
+            else if let KeyCode::Char(ch) = key_pressed {
+                first.push(ch);
+            }
+        } else {
+            if key_pressed == KeyCode::Left {pos = 0}
+            else if key_pressed == KeyCode::Backspace {
+                if second.chars().count() > 0 {second.pop();}
+            }
+            else if let KeyCode::Char(ch) = key_pressed {
+                second.push(ch);
+            }
+        }
+
+        io::stdout().execute(crossterm::cursor::MoveUp(lines)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+        if key_pressed == KeyCode::Esc {
+            match parse(&first, &second) {
+                Ok(parsed) => return parsed,
+                Err(e) => error = Some(e)
+            }
+        } else if error.is_some() {
+            error = parse(&first, &second).err();
+        }
+    }
+}
+
+/// parses the text entered for a distribution parameter, giving `None` if it was left blank
+/// to be estimated. the value must pass `valid`, which `requirement` describes, like "positive"
+fn parse_parameter(text: &str, name: &str, valid: impl Fn(f64) -> bool, requirement: &str) -> Result<Option<f64>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    match text.parse::<f64>() {
+        Ok(value) if valid(value) => Ok(Some(value)),
+        Ok(_) => Err(format!("{name} must be {requirement}")),
+        Err(_) => Err(format!("{name} must be a number, not '{text}'"))
+    }
+}
+
+fn create_binomial_distribution() -> (usize, BinomialP) {
+    edit_distribution_parameters("B", ["n", "p"], "leave p blank for estimation", |n, p| {
+        let n = match n.trim() {
+            "" => return Err(String::from("n, the number of trials, must be given")),
+            n => n.parse::<usize>().map_err(|_| format!("n must be a whole number, not '{n}'"))?
+        };
+        if n >= stats::MAX_VALUES {
+            return Err(format!("n can be at most {}", stats::MAX_VALUES - 1));
+        }
+        let p = match parse_parameter(p, "p", |p| (0. ..=1.).contains(&p), "between 0 and 1")? {
+            Some(p) => BinomialP::P(p),
+            None => BinomialP::Estimate
+        };
+        Ok((n, p))
+    })
+}
+
+/// prints the (grouped) observed and expected frequencies of a goodness of fit test,
+/// highlighting any expected frequencies below 5
+fn print_goodness_of_fit(result: &stats::GoodnessOfFit) {
+    let display_table = vec![
+        result.observed.iter().map(|o| format_frequency(*o)).collect(),
+        result.expected.iter().map(|e| e.to_string()).collect::<Vec<String>>()
+    ];
+    let model = match result.model {
+        stats::Model::Given => String::from("type"),
+        model => model.to_string()
+    };
+    let row_labels = vec![model, String::from("Observed"), String::from("Expected")];
+    let low_expecteds: Vec<(usize, usize)> = result.low_expected_classes().into_iter().map(|j| (1, j)).collect();
+
+    print_table(&display_table, &result.class_labels, &row_labels, TableDisplay {
+        pos: (display_table.len()+1, display_table[0].len()+1),
+        highlighted: &low_expecteds,
+        invalid: &[],
+        text_cursor: None,
+        overflow: Overflow::Wrap,
+    });
+}
+
+pub fn binomial(significance: Significance) {
+    display_title("Binomial");
+
+    let (n, p) = create_binomial_distribution();
+    let minimum = minimum_expected_input();
+
+    // columns can be added or removed, but X still only goes up to n
+    let observed = edit_observed_counts(0, Some(n+1), Some(n));
+    match stats::binomial(n, p, &observed, minimum) {
+        Ok(result) => print_binomial(&result, p, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a binomial fit
+pub fn print_binomial(result: &stats::GoodnessOfFit, p: BinomialP, significance: Significance) {
+    let n = match result.model {
+        stats::Model::Binomial { n, .. } => n,
+        _ => panic!("result is not from a binomial fit")
+    };
+
+    print_goodness_of_fit(result);
+    let model = match p {
+        BinomialP::P(p) => format!("B({n}, {p})"),
+        BinomialP::Estimate => format!("B({n}, p)")
+    };
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+/// edits the parameter of a distribution, shown as `X ~ distribution(name: _)` with a hint
+/// after it, until `parse` accepts the text entered, showing what's wrong with it like
+/// [`edit_distribution_parameters`]
+fn edit_distribution_parameter<T>(distribution: &str, name: &str, hint: &str, parse: impl Fn(&str) -> Result<T, String>) -> T {
+    let mut parameter: String = String::from("");
+    // problems are only shown once the user has tried to finish
+    let mut error: Option<String> = None;
+
+    io::stdout().execute(crossterm::cursor::Hide).unwrap();
+    loop {
+        println!("X ~ {distribution}({name}: {}{}{}{}) {}({hint}){}",
+        back::WHITE,
+        text::BLACK,
+        if !parameter.is_empty() {&parameter} else {" "},
+        style::RESET_ALL,
+        text::MAGENTA,
+        style::RESET_ALL
+        );
+        let mut lines = 3;
+        if let Some(error) = &error {
+            println!("\n{}{error}{}", text::RED, text::RESET);
+            lines += 2;
+        }
+        println!("\npress {}[esc]{} to finish",
+            text::LIGHT_BLUE,
+            text::RESET,
+        );
+
+        let key_pressed = get_key_pressed();
+        if let KeyCode::Char(ch) = key_pressed {
+            parameter.push(ch);
+        }
+        else if key_pressed == KeyCode::Backspace && !parameter.is_empty() {
+            parameter.pop();
+        }
+
+        io::stdout().execute(crossterm::cursor::MoveUp(lines)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+        if key_pressed == KeyCode::Esc {
+            match parse(&parameter) {
+                Ok(parsed) => return parsed,
+                Err(e) => error = Some(e)
+            }
+        } else if error.is_some() {
+            error = parse(&parameter).err();
+        }
+    }
+}
+
+/// reads the observed frequencies of a count from a file or the table editor, with each
+/// column labelled by its value of X, returning the frequency of each value from `first_value`
+/// up to `last_value`, or the largest label without one (see [`stats::value_frequencies`]).
+/// the table starts off with `columns` columns labelled from `first_value`, or as many as
+/// are asked for if it's `None`
+fn edit_observed_counts(first_value: usize, columns: Option<usize>, last_value: Option<usize>) -> Vec<f64> {
+    io::stdout().execute(crossterm::cursor::Show).unwrap();
+    let frequencies = frequencies_input();
+    let imported = import_input(|table| table.series(1));
+    let columns = match (&imported, columns) {
+        (Some(series), _) => series.values[0].len(),
+        (None, Some(columns)) => columns,
+        (None, None) => range_input("enter the number of columns:", 1, u32::MAX) as usize
+    };
+
+    let mut observed_table = vec![vec![String::from(""); columns]];
+    let mut column_labels = Vec::with_capacity(columns);
+    for i in 0..columns {
+        column_labels.push((first_value + i).to_string());
+    }
+    let mut row_labels = vec![String::from("type"), String::from("Observed")];
+
+    if let Some(series) = imported {
+        observed_table = series.values;
+        if let Some(labels) = series.labels {column_labels = labels}
+    }
+
+    edit_table(&mut observed_table, &mut column_labels, &mut row_labels, Resizing::COLUMNS, |observed_table, column_labels, _| {
+        let (_, observed) = both(
+            parse_column_labels(column_labels, |label| stats::parse_value_label(label, first_value, last_value)),
+            parse_cells(observed_table, |_, cell| frequencies.parse(cell))
+        )?;
+        stats::value_frequencies(column_labels, &observed[0], first_value, last_value).map_err(|error| vec![TableError::whole(error)])
+    })
+}
+
+fn create_poission_distribution() -> PoissonMean {
+    edit_distribution_parameter("Po", "λ", "leave λ blank for esitmation", |mean| {
+        match parse_parameter(mean, "λ", |mean| mean >= 0. && mean.is_finite(), "0 or more")? {
+            Some(mean) => Ok(PoissonMean::Mean(mean)),
+            None => Ok(PoissonMean::Estimate)
+        }
+    })
+}
+
+pub fn poission(significance: Significance) {
+    display_title("Possion");
+
+    let mean = create_poission_distribution();
+    let minimum = minimum_expected_input();
+
+    let observed = edit_observed_counts(0, None, None);
+    match stats::poisson(mean, &observed, minimum) {
+        Ok(result) => print_poisson(&result, mean, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a poisson fit
+pub fn print_poisson(result: &stats::GoodnessOfFit, mean: PoissonMean, significance: Significance) {
+    print_goodness_of_fit(result);
+    let model = match mean {
+        PoissonMean::Mean(mean) => format!("Po({mean})"),
+        PoissonMean::Estimate => String::from("Po(λ)")
+    };
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+fn create_geometric_distribution() -> GeometricP {
+    edit_distribution_parameter("Geo", "p", "leave p blank for estimation", |p| {
+        match parse_parameter(p, "p", |p| 0. < p && p <= 1., "greater than 0 and at most 1")? {
+            Some(p) => Ok(GeometricP::P(p)),
+            None => Ok(GeometricP::Estimate)
+        }
+    })
+}
+
+pub fn geometric(significance: Significance) {
+    display_title("Geometric");
+
+    let p = create_geometric_distribution();
+    let minimum = minimum_expected_input();
+
+    // X is the number of trials, so starts from 1
+    let observed = edit_observed_counts(1, None, None);
+    match stats::geometric(p, &observed, minimum) {
+        Ok(result) => print_geometric(&result, p, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a geometric fit
+pub fn print_geometric(result: &stats::GoodnessOfFit, p: GeometricP, significance: Significance) {
+    print_goodness_of_fit(result);
+    let model = match p {
+        GeometricP::P(p) => format!("Geo({p})"),
+        GeometricP::Estimate => String::from("Geo(p)")
+    };
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+fn create_normal_distribution() -> (NormalMean, NormalSd) {
+    edit_distribution_parameters("N", ["μ", "σ"], "leave μ or σ blank for estimation", |mean, sd| {
+        let mean = match parse_parameter(mean, "μ", |mean| mean.is_finite(), "finite")? {
+            Some(mean) => NormalMean::Mean(mean),
+            None => NormalMean::Estimate
+        };
+        let sd = match parse_parameter(sd, "σ", |sd| sd > 0. && sd.is_finite(), "positive")? {
+            Some(sd) => NormalSd::Sd(sd),
+            None => NormalSd::Estimate
+        };
+        Ok((mean, sd))
+    })
+}
+
+fn create_negative_binomial_distribution() -> (NegativeBinomialR, NegativeBinomialP) {
+    edit_distribution_parameters("NB", ["r", "p"], "leave r or p blank for estimation", |r, p| {
+        let r = match parse_parameter(r, "r", |r| r > 0. && r.is_finite(), "positive")? {
+            Some(r) => NegativeBinomialR::R(r),
+            None => NegativeBinomialR::Estimate
+        };
+        let p = match parse_parameter(p, "p", |p| 0. < p && p <= 1., "greater than 0 and at most 1")? {
+            Some(p) => NegativeBinomialP::P(p),
+            None => NegativeBinomialP::Estimate
+        };
+        Ok((r, p))
+    })
+}
+
+pub fn negative_binomial(significance: Significance) {
+    display_title("Negative Binomial");
+
+    let (r, p) = create_negative_binomial_distribution();
+    let minimum = minimum_expected_input();
+
+    let observed = edit_observed_counts(0, None, None);
+    match stats::negative_binomial(r, p, &observed, minimum) {
+        Ok(result) => print_negative_binomial(&result, r, p, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a negative binomial fit
+pub fn print_negative_binomial(result: &stats::GoodnessOfFit, r: NegativeBinomialR, p: NegativeBinomialP, significance: Significance) {
+    print_goodness_of_fit(result);
+    let r = match r {
+        NegativeBinomialR::R(r) => r.to_string(),
+        NegativeBinomialR::Estimate => String::from("r")
+    };
+    let p = match p {
+        NegativeBinomialP::P(p) => p.to_string(),
+        NegativeBinomialP::Estimate => String::from("p")
+    };
+    let model = format!("NB({r}, {p})");
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+/// reads grouped continuous data from a file or the table editor, returning
+/// the class interval labels (like 10-20) and the observed frequency of each
+fn edit_class_intervals() -> (Vec<String>, Vec<f64>) {
+    io::stdout().execute(crossterm::cursor::Show).unwrap();
+    let frequencies = frequencies_input();
+    let imported = import_input(|table| table.series(1));
+    let columns = match &imported {
+        Some(series) => series.values[0].len(),
+        None => range_input("enter the number of classes:", 1, u32::MAX) as usize
+    };
+
+    // the column labels are the class intervals, like 10-20
+    let mut observed_table = vec![vec![String::from(""); columns]];
+    let mut column_labels = vec![String::from("---"); columns];
+    let mut row_labels = vec![String::from("class"), String::from("Observed")];
+
+    if let Some(series) = imported {
+        observed_table = series.values;
+        if let Some(labels) = series.labels {column_labels = labels}
+    }
+
+    edit_table(&mut observed_table, &mut column_labels, &mut row_labels, Resizing::COLUMNS, |observed_table, column_labels, _| {
+        let (_, observed) = both(
+            parse_column_labels(column_labels, stats::parse_class_interval),
+            parse_cells(observed_table, |_, cell| frequencies.parse(cell))
+        )?;
+        Ok((column_labels.clone(), observed[0].clone()))
+    })
+}
+
+pub fn normal(significance: Significance) {
+    display_title("Normal");
+
+    let (mean, sd) = create_normal_distribution();
+    let minimum = minimum_expected_input();
+
+    let (classes, observed) = edit_class_intervals();
+    match stats::normal(mean, sd, &classes, &observed, minimum) {
+        Ok(result) => print_normal(&result, mean, sd, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a normal fit
+pub fn print_normal(result: &stats::GoodnessOfFit, mean: NormalMean, sd: NormalSd, significance: Significance) {
+    print_goodness_of_fit(result);
+    let mean = match mean {
+        NormalMean::Mean(mean) => mean.to_string(),
+        NormalMean::Estimate => String::from("μ")
+    };
+    let variance = match sd {
+        NormalSd::Sd(sd) => format!("{sd}²"),
+        NormalSd::Estimate => String::from("σ²")
+    };
+    let model = format!("N({mean}, {variance})");
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+fn create_uniform_distribution() -> UniformRange {
+    edit_distribution_parameters("U", ["a", "b"], "leave a and b blank to use the range of the classes", |a, b| {
+        let a = parse_parameter(a, "a", |a| a.is_finite(), "finite")?;
+        let b = parse_parameter(b, "b", |b| b.is_finite(), "finite")?;
+        match (a, b) {
+            (None, None) => Ok(UniformRange::Classes),
+            (Some(a), Some(b)) if a < b => Ok(UniformRange::Range(a, b)),
+            (Some(_), Some(_)) => Err(String::from("a must be below b")),
+            _ => Err(String::from("give both a and b, or leave both blank to use the range of the classes"))
+        }
+    })
+}
+
+pub fn uniform(significance: Significance) {
+    display_title("Continuous Uniform");
+
+    let range = create_uniform_distribution();
+    let minimum = minimum_expected_input();
+
+    let (classes, observed) = edit_class_intervals();
+    match stats::uniform(range, &classes, &observed, minimum) {
+        Ok(result) => print_uniform(&result, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of a continuous uniform fit
+pub fn print_uniform(result: &stats::GoodnessOfFit, significance: Significance) {
+    let (a, b) = match result.model {
+        stats::Model::Uniform { a, b } => (a, b),
+        _ => panic!("result is not from a uniform fit")
+    };
+
+    print_goodness_of_fit(result);
+    let model = format!("U({a}, {b})");
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+fn create_exponential_distribution() -> ExponentialRate {
+    edit_distribution_parameter("Exp", "λ", "leave λ blank for estimation", |rate| {
+        match parse_parameter(rate, "λ", |rate| rate > 0. && rate.is_finite(), "positive")? {
+            Some(rate) => Ok(ExponentialRate::Rate(rate)),
+            None => Ok(ExponentialRate::Estimate)
+        }
+    })
+}
+
+pub fn exponential(significance: Significance) {
+    display_title("Exponential");
+
+    let rate = create_exponential_distribution();
+    let minimum = minimum_expected_input();
+
+    let (classes, observed) = edit_class_intervals();
+    match stats::exponential(rate, &classes, &observed, minimum) {
+        Ok(result) => print_exponential(&result, rate, significance),
+        Err(error) => println!("{}{error}{}", text::RED, text::RESET)
+    }
+}
+
+/// prints the grouped table and test result of an exponential fit
+pub fn print_exponential(result: &stats::GoodnessOfFit, rate: ExponentialRate, significance: Significance) {
+    print_goodness_of_fit(result);
+    let model = match rate {
+        ExponentialRate::Rate(rate) => format!("Exp({rate})"),
+        ExponentialRate::Estimate => String::from("Exp(λ)")
+    };
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        &format!("{model} is a suitable model for the data"),
+        &format!("{model} is not a suitable model for the data")
+    );
+}
+
+pub fn contingency_table(significance: Significance) {
+    display_title("Contingency Table");
+
+    let frequencies = frequencies_input();
+    let imported = import_input(|table| Ok(table.clone()));
+    let (rows, columns) = match &imported {
+        Some(table) => (table.rows(), table.columns()),
+        None => (
+            range_input("enter the number of rows:", 1, u32::MAX) as usize,
+            range_input("enter the number of columns:", 1, u32::MAX) as usize
+        )
+    };
+
+    let mut table = vec![vec![String::from(""); columns]; rows];
+    let mut column_labels = vec![String::from("---"); columns];
+    let mut row_labels = vec![String::from("---"); rows+1];
+
+    if let Some(imported) = imported {
+        table = imported.cells;
+        if let Some(labels) = imported.column_labels {column_labels = labels}
+        if let Some(label) = imported.corner_label {row_labels[0] = label}
+        if let Some(labels) = imported.row_labels {
+            for (i, label) in labels.into_iter().enumerate() {
+                row_labels[i+1] = label;
+            }
+        }
+    }
+
+    let observed = edit_table(&mut table, &mut column_labels, &mut row_labels, Resizing::ROWS_AND_COLUMNS, |table, _, _| {
+        if table.len() < 2 || table[0].len() < 2 {
+            return Err(vec![TableError::whole(String::from("a contingency table needs at least 2 rows and 2 columns"))]);
+        }
+        let observed = parse_cells(table, |_, cell| frequencies.parse(cell))?;
+
+        // an empty row or column leaves nothing to expect in it
+        let mut errors = Vec::new();
+        for (i, row) in observed.iter().enumerate() {
+            if row.iter().sum::<f64>() == 0. {
+                errors.push(TableError::at((i+1, 0), format!("row {} has a total of 0", i+1)));
+            }
+        }
+        for j in 0..observed[0].len() {
+            if observed.iter().map(|row| row[j]).sum::<f64>() == 0. {
+                errors.push(TableError::at((0, j+1), format!("column {} has a total of 0", j+1)));
+            }
+        }
+        if errors.is_empty() {Ok(observed)} else {Err(errors)}
+    });
+
+    let mut result = match stats::contingency_test(&observed) {
+        Ok(result) => result,
+        Err(error) => {
+            println!("{}{error}{}", text::RED, text::RESET);
+            return;
+        }
+    };
+    result.column_labels = column_labels;
+    result.row_labels = row_labels[1..].to_vec();
+    let mut result = merge_contingency_table(result, &row_labels[0]);
+    if result.df == 1 {
+        result.set_yates_correction(yes_no_input("apply Yates' continuity correction? (Y/n)", true));
+    }
+    // the exact test runs by itself when expecteds are low (once merging is done), otherwise it's optional
+    if result.has_low_expecteds() || yes_no_input("also run Fisher's exact test? (y/N)", false) {
+        result.set_exact_test(true);
+    }
+
+    print_contingency_table(&result, &row_labels[0], significance);
+}
+
+/// while any expected frequency is below 5, shows them highlighted and offers to merge
+/// adjacent rows or columns, either automatically or by choosing them
+fn merge_contingency_table(mut result: stats::ContingencyTest, corner_label: &str) -> stats::ContingencyTest {
+    let mut redrawn = false;
+    loop {
+        let (rows, columns) = (result.observed.len() as u32, result.column_totals.len() as u32);
+        if !result.has_low_expecteds() || (rows <= 2 && columns <= 2) {break}
+
+        display_title("Contingency Table");
+        redrawn = true;
+        print_contingency_cells(&result, corner_label);
+        print_low_expected_warning();
+        // a table needs at least 2 rows and 2 columns left to test, so say why an option can't be used
+        let unavailable = |lines: u32, what: &str| match lines {
+            2 => format!(" {}(not available, as there are only 2 {what}){}", text::RED, text::RESET),
+            _ => String::new()
+        };
+        println!(
+            "\n [1] merge automatically\n [2] merge two adjacent rows{}\n [3] merge two adjacent columns{}\n [4] continue without merging\n",
+            unavailable(rows, "rows"),
+            unavailable(columns, "columns")
+        );
+
+        match range_input("choose an option:", 1, 4) {
+            1 => result = result.merge_low_expecteds(),
+            2 if rows > 2 => {
+                let first = range_input(&format!("enter the first of the two rows to merge (1 to {}):", rows-1), 1, rows-1);
+                result = result.merge_rows(first as usize - 1);
+            },
+            3 if columns > 2 => {
+                let first = range_input(&format!("enter the first of the two columns to merge (1 to {}):", columns-1), 1, columns-1);
+                result = result.merge_columns(first as usize - 1);
+            },
+            4 => break,
+            _ => {}
+        }
+    }
+
+    if redrawn {
+        display_title("Contingency Table");
+    }
+    result
+}
+
+/// prints each cell's observed and expected frequencies, highlighting expected frequencies below 5
+fn print_contingency_cells(result: &stats::ContingencyTest, corner_label: &str) {
+    let (rows, columns) = (result.observed.len(), result.column_totals.len());
+    let display_table: Vec<Vec<String>> = result.observed.iter().zip(result.expected.iter())
+        .map(|(o_row, e_row)| o_row.iter().zip(e_row.iter()).map(|(o, e)| format!("{}, {e:.2}", format_frequency(*o))).collect())
+        .collect();
+
+    let mut row_labels = vec![corner_label.to_string()];
+    row_labels.extend(result.row_labels.iter().cloned());
+
+    print_table(&display_table, &result.column_labels, &row_labels, TableDisplay {
+        pos: (rows+1, columns+1),
+        highlighted: &result.low_expected_cells(),
+        invalid: &[],
+        text_cursor: None,
+        overflow: Overflow::Wrap,
+    });
+}
+
+/// prints each cell's observed and expected frequencies and the test result of a contingency table
+pub fn print_contingency_table(result: &stats::ContingencyTest, corner_label: &str, significance: Significance) {
+    print_contingency_cells(result, corner_label);
+    print_test_result(
+        result.statistic,
+        result.yates_statistic,
+        result.df,
+        significance,
+        "there is no association between the rows and columns",
+        "there is an association between the rows and columns"
+    );
+
+    if result.has_low_expecteds() {
+        print_low_expected_warning();
+    }
+    if let Some(exact_p_value) = result.exact_p_value {
+        let test = if result.observed.len() == 2 && result.column_totals.len() == 2 {"Fisher's exact test"} else {"Fisher-Freeman-Halton exact test"};
+        println!("\n{test} p-value = {}{}{}", text::GREEN, exact_p_value, text::RESET);
+        if exact_p_value < significance.proportion() {
+            println!("{}so reject H0 at the {significance} level{}", text::RED, text::RESET);
+        } else {
+            println!("{}so do not reject H0 at the {significance} level{}", text::GREEN, text::RESET);
+        }
+    }
+    if let Some(error) = &result.exact_error {
+        println!("\n{}Fisher's exact test couldn't be run: {error}{}", text::RED, text::RESET);
+    }
+}
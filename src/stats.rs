@@ -0,0 +1,1560 @@
+//! the chi-squared tests themselves, free of any terminal input/output
+//!
+//! # Example
+//! ```
+//! use chi_squared::stats::{self, MinimumExpected, PoissonMean};
+//!
+//! let result = stats::poisson(PoissonMean::Estimate, &[12., 25., 20., 18., 10., 15.], MinimumExpected::default())?;
+//! println!("X² = {} with {} degrees of freedom", result.statistic, result.df);
+//! # Ok::<(), String>(())
+//! ```
+
+use crate::distribution::{self, ChiSquared};
+use crate::exact;
+use std::fmt;
+
+/// the most values of X a count can have a frequency for, so a stray label like
+/// 99999999999 is an error rather than a table too big to fit in memory
+pub const MAX_VALUES: usize = 1_000_000;
+
+/// what observed frequencies can be: whole counts, or any number that isn't negative,
+/// such as weighted survey counts or rescaled frequencies
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Frequencies {
+    #[default]
+    Counts,
+    Weighted
+}
+
+impl Frequencies {
+    /// reads an observed frequency
+    pub fn parse(&self, value: &str) -> Result<f64, String> {
+        let value = value.trim();
+        let frequency = match value.parse::<f64>() {
+            Ok(frequency) if frequency.is_finite() => frequency,
+            _ => return Err(format!("'{value}' is not a number")),
+        };
+        if frequency < 0. {
+            return Err(format!("'{value}' is negative"));
+        }
+        if *self == Frequencies::Counts && frequency.fract() != 0. {
+            return Err(format!("'{value}' is not a whole number count"));
+        }
+
+        Ok(frequency)
+    }
+}
+
+/// the probability of success for a binomial model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinomialP {
+    P(f64),
+    Estimate
+}
+
+/// the mean of a poisson model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoissonMean {
+    Mean(f64),
+    Estimate
+}
+
+/// the probability of success for a geometric model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometricP {
+    P(f64),
+    Estimate
+}
+
+/// the number of successes of a negative binomial model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NegativeBinomialR {
+    R(f64),
+    Estimate
+}
+
+/// the probability of success for a negative binomial model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NegativeBinomialP {
+    P(f64),
+    Estimate
+}
+
+/// the mean of a normal model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NormalMean {
+    Mean(f64),
+    Estimate
+}
+
+/// the standard deviation of a normal model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NormalSd {
+    Sd(f64),
+    Estimate
+}
+
+/// the range of a continuous uniform model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UniformRange {
+    Range(f64, f64),
+    /// from the lower bound of the first class to the upper bound of the last
+    Classes
+}
+
+/// the rate of an exponential model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExponentialRate {
+    Rate(f64),
+    Estimate
+}
+
+/// where the expected frequencies of an O vs E test come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectedFrequencies {
+    /// given for each class
+    Given(Vec<f64>),
+    /// every class equally likely
+    Uniform,
+    /// in proportion to these parts, such as 9:3:3:1
+    Ratio(Vec<f64>),
+    /// the proportion of the total in each class, adding up to 1
+    Proportions(Vec<f64>),
+}
+
+impl ExpectedFrequencies {
+    /// the expected frequency of each class, sharing out the observed total for
+    /// anything but given expecteds
+    pub fn expecteds(&self, observed: &[f64]) -> Result<Vec<f64>, String> {
+        let freq_sum: f64 = observed.iter().sum();
+        let classes = observed.len();
+        let (expected, what) = match self {
+            ExpectedFrequencies::Given(expected) => (expected.clone(), "expected frequencies"),
+            ExpectedFrequencies::Uniform => (vec![freq_sum / classes as f64; classes], "classes"),
+            ExpectedFrequencies::Ratio(parts) => {
+                let parts_sum: f64 = parts.iter().sum();
+                (parts.iter().map(|part| freq_sum * part / parts_sum).collect(), "parts in the ratio")
+            },
+            ExpectedFrequencies::Proportions(proportions) => {
+                check_proportions(proportions)?;
+                (proportions.iter().map(|proportion| freq_sum * proportion).collect(), "proportions")
+            },
+        };
+
+        if expected.len() != classes {
+            return Err(format!("there are {} {what} but {classes} observed frequencies", expected.len()));
+        }
+        if freq_sum <= 0. {
+            return Err(String::from("the observed frequencies add up to 0"));
+        }
+        if expected.iter().any(|e| !(*e > 0. && e.is_finite())) {
+            return Err(String::from("every expected frequency must be positive"));
+        }
+
+        Ok(expected)
+    }
+}
+
+/// reads a ratio like 9:3:3:1 (or 9,3,3,1) into its parts, which must all be positive
+pub fn parse_ratio(ratio: &str) -> Result<Vec<f64>, String> {
+    let separator = if ratio.contains(':') {':'} else {','};
+    let mut parts = Vec::new();
+    for part in ratio.split(separator) {
+        match part.trim().parse::<f64>() {
+            Ok(part) if part > 0. && part.is_finite() => parts.push(part),
+            _ => return Err(format!("'{}' is not a positive part of a ratio", part.trim())),
+        }
+    }
+    if parts.len() < 2 {
+        return Err(String::from("a ratio needs at least 2 parts"));
+    }
+
+    Ok(parts)
+}
+
+/// reads a comma separated list of proportions, which must add up to 1
+pub fn parse_proportions(proportions: &str) -> Result<Vec<f64>, String> {
+    let mut parsed = Vec::new();
+    for proportion in proportions.split(',') {
+        match proportion.trim().parse::<f64>() {
+            Ok(proportion) => parsed.push(proportion),
+            Err(_) => return Err(format!("'{}' is not a proportion", proportion.trim())),
+        }
+    }
+    check_proportions(&parsed)?;
+
+    Ok(parsed)
+}
+
+/// proportions must each be above 0 and add up to 1 (allowing for rounding)
+fn check_proportions(proportions: &[f64]) -> Result<(), String> {
+    if proportions.iter().any(|p| !(*p > 0. && *p <= 1.)) {
+        return Err(String::from("every proportion must be above 0 and at most 1"));
+    }
+    let sum: f64 = proportions.iter().sum();
+    if (sum - 1.).abs() > 1e-6 {
+        return Err(format!("the proportions add up to {sum}, not 1"));
+    }
+
+    Ok(())
+}
+
+/// the distribution the expected frequencies of a goodness of fit test came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Model {
+    /// expected frequencies given directly
+    Given,
+    Binomial { n: usize, p: f64 },
+    Poisson { mean: f64 },
+    Geometric { p: f64 },
+    NegativeBinomial { r: f64, p: f64 },
+    Normal { mean: f64, sd: f64 },
+    Uniform { a: f64, b: f64 },
+    Exponential { rate: f64 },
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Model::Given => write!(f, "given expecteds"),
+            Model::Binomial { n, p } => write!(f, "X ~ B({n}, {p})"),
+            Model::Poisson { mean } => write!(f, "X ~ Po({mean})"),
+            Model::Geometric { p } => write!(f, "X ~ Geo({p})"),
+            Model::NegativeBinomial { r, p } => write!(f, "X ~ NB({r}, {p})"),
+            Model::Normal { mean, sd } => write!(f, "X ~ N({mean}, {sd}²)"),
+            Model::Uniform { a, b } => write!(f, "X ~ U({a}, {b})"),
+            Model::Exponential { rate } => write!(f, "X ~ Exp({rate})"),
+        }
+    }
+}
+
+/// the result of a goodness of fit test
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoodnessOfFit {
+    pub model: Model,
+    /// the names of the model's parameters that were estimated from the data,
+    /// such as "p", "lambda", "r", "mean" or "sd"
+    pub estimated: Vec<&'static str>,
+    /// label of each class after grouping, numbered from 1 for given expecteds unless changed
+    pub class_labels: Vec<String>,
+    /// observed frequency of each class after grouping
+    pub observed: Vec<f64>,
+    /// expected frequency of each class after grouping
+    pub expected: Vec<f64>,
+    /// which values of the fitted distribution make up each class, if it was grouped
+    pub grouping: Option<Grouping>,
+    /// the uncorrected statistic
+    pub statistic: f64,
+    /// the statistic with yates' continuity correction, if it's being applied
+    pub yates_statistic: Option<f64>,
+    /// whether yates' correction is applied whenever there's 1 degree of freedom,
+    /// which carries over to merged tests
+    pub yates_correction: bool,
+    pub df: usize,
+}
+
+impl GoodnessOfFit {
+    /// how many of the model's parameters were estimated from the data
+    pub fn estimated_parameters(&self) -> usize {
+        self.estimated.len()
+    }
+
+    /// (O - E)² / E for each class, which sum to the statistic
+    pub fn contributions(&self) -> Vec<f64> {
+        self.observed.iter().zip(self.expected.iter()).map(|(o, e)| (o - e).powi(2) / e).collect()
+    }
+
+    /// the classes with an expected frequency below 5, where the X² approximation isn't valid
+    pub fn low_expected_classes(&self) -> Vec<usize> {
+        (0..self.expected.len()).filter(|i| self.expected[*i] < 5.).collect()
+    }
+
+    pub fn has_low_expecteds(&self) -> bool {
+        self.expected.iter().any(|e| *e < 5.)
+    }
+
+    /// the test again with class `first` merged into the class after it, labelled "a+b".
+    /// yates' correction is applied if the merge leaves 1 degree of freedom and it's turned on
+    ///
+    /// # Panics
+    /// if `first` is the last class, or the merge would leave no degrees of freedom
+    pub fn merge_classes(&self, first: usize) -> GoodnessOfFit {
+        assert!(first+1 < self.observed.len(), "there is no class after the one being merged");
+        assert!(self.df > 1, "merging would leave no degrees of freedom");
+
+        let mut observed = self.observed.clone();
+        let mut expected = self.expected.clone();
+        let (next_observed, next_expected) = (observed.remove(first+1), expected.remove(first+1));
+        observed[first] += next_observed;
+        expected[first] += next_expected;
+
+        let mut result = GoodnessOfFit {
+            model: self.model,
+            estimated: self.estimated.clone(),
+            class_labels: merge_labels(&self.class_labels, first),
+            statistic: chi_squared_statistic(&observed, &expected),
+            yates_statistic: None,
+            yates_correction: self.yates_correction,
+            df: self.df - 1,
+            observed,
+            expected,
+            grouping: self.grouping.clone().map(|mut grouping| {grouping.merge(first); grouping}),
+        };
+        result.set_yates_correction(self.yates_correction);
+
+        result
+    }
+
+    /// repeatedly merges the class with the lowest expected frequency into its smaller neighbour
+    /// until every expected frequency is at least 5, or there's only 1 degree of freedom left
+    pub fn merge_low_expecteds(&self) -> GoodnessOfFit {
+        let mut result = self.clone();
+        while result.has_low_expecteds() && result.df > 1 {
+            let lowest = lowest_index(&result.expected);
+            let first = merge_neighbour(&result.expected, lowest);
+            result = result.merge_classes(first);
+        }
+
+        result
+    }
+
+    /// turns yates' continuity correction on or off.
+    /// it's only ever applied with 1 degree of freedom
+    pub fn set_yates_correction(&mut self, apply: bool) {
+        self.yates_correction = apply;
+        self.yates_statistic = if apply && self.df == 1 {
+            Some(yates_statistic(&self.observed, &self.expected))
+        } else {
+            None
+        };
+    }
+
+    /// the statistic the test is decided on: the corrected one if yates' correction is applied
+    pub fn test_statistic(&self) -> f64 {
+        self.yates_statistic.unwrap_or(self.statistic)
+    }
+
+    pub fn p_value(&self) -> f64 {
+        ChiSquared::new(self.df).sf(self.test_statistic())
+    }
+
+    /// the value the statistic must exceed to reject H0 at the given significance (as a proportion)
+    pub fn critical_value(&self, significance: f64) -> f64 {
+        ChiSquared::new(self.df).critical_value(significance)
+    }
+}
+
+/// the result of a test for association in a contingency table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContingencyTest {
+    /// label of each row, numbered from 1 unless changed
+    pub row_labels: Vec<String>,
+    /// label of each column, numbered from 1 unless changed
+    pub column_labels: Vec<String>,
+    pub observed: Vec<Vec<f64>>,
+    pub expected: Vec<Vec<f64>>,
+    pub row_totals: Vec<f64>,
+    pub column_totals: Vec<f64>,
+    pub grand_total: f64,
+    /// the uncorrected statistic
+    pub statistic: f64,
+    /// the statistic with yates' continuity correction, if it's being applied
+    pub yates_statistic: Option<f64>,
+    /// whether yates' correction is applied whenever there's 1 degree of freedom,
+    /// which carries over to merged tests
+    pub yates_correction: bool,
+    pub df: usize,
+    /// p-value of fisher's exact test, if it's been run
+    pub exact_p_value: Option<f64>,
+    /// why fisher's exact test couldn't be run, if it was tried and failed
+    pub exact_error: Option<String>,
+}
+
+impl ContingencyTest {
+    /// whether any expected frequency is below 5, where the X² approximation isn't valid
+    pub fn has_low_expecteds(&self) -> bool {
+        self.expected.iter().flatten().any(|e| *e < 5.)
+    }
+
+    /// the (row, column) of each cell with an expected frequency below 5
+    pub fn low_expected_cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for i in 0..self.expected.len() {
+            for j in 0..self.expected[i].len() {
+                if self.expected[i][j] < 5. {
+                    cells.push((i, j));
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// the test again with row `first` merged into the row after it, labelled "a+b".
+    /// fisher's exact test isn't run on the merged table until [`Self::set_exact_test`] is called
+    ///
+    /// # Panics
+    /// if `first` is the last row, or there are only 2 rows
+    pub fn merge_rows(&self, first: usize) -> ContingencyTest {
+        assert!(first+1 < self.observed.len(), "there is no row after the one being merged");
+        assert!(self.observed.len() > 2, "merging would leave fewer than 2 rows");
+
+        let mut observed = self.observed.clone();
+        let next = observed.remove(first+1);
+        for j in 0..next.len() {
+            observed[first][j] += next[j];
+        }
+
+        let mut result = contingency_test(&observed).expect("merged rows still make a table to test");
+        result.row_labels = merge_labels(&self.row_labels, first);
+        result.column_labels = self.column_labels.clone();
+        result.set_yates_correction(self.yates_correction);
+        result
+    }
+
+    /// the test again with column `first` merged into the column after it, labelled "a+b".
+    /// fisher's exact test isn't run on the merged table until [`Self::set_exact_test`] is called
+    ///
+    /// # Panics
+    /// if `first` is the last column, or there are only 2 columns
+    pub fn merge_columns(&self, first: usize) -> ContingencyTest {
+        assert!(first+1 < self.column_totals.len(), "there is no column after the one being merged");
+        assert!(self.column_totals.len() > 2, "merging would leave fewer than 2 columns");
+
+        let mut observed = self.observed.clone();
+        for row in observed.iter_mut() {
+            let next = row.remove(first+1);
+            row[first] += next;
+        }
+
+        let mut result = contingency_test(&observed).expect("merged columns still make a table to test");
+        result.row_labels = self.row_labels.clone();
+        result.column_labels = merge_labels(&self.column_labels, first);
+        result.set_yates_correction(self.yates_correction);
+        result
+    }
+
+    /// repeatedly finds the cell with the lowest expected frequency and merges its row or column
+    /// (whichever has the smaller total) into its smaller neighbour, until every expected
+    /// frequency is at least 5 or the table is down to 2×2. like the other merges, fisher's
+    /// exact test isn't run on the merged table until [`Self::set_exact_test`] is called
+    pub fn merge_low_expecteds(&self) -> ContingencyTest {
+        if !self.has_low_expecteds() {
+            return self.clone();
+        }
+
+        let mut result = self.clone();
+        while result.has_low_expecteds() && (result.row_totals.len() > 2 || result.column_totals.len() > 2) {
+            let expecteds: Vec<f64> = result.expected.iter().flatten().copied().collect();
+            let lowest = lowest_index(&expecteds);
+            let (row, column) = (lowest / result.column_totals.len(), lowest % result.column_totals.len());
+
+            // merging a line of 2 would leave nothing to test
+            let merge_row = if result.row_totals.len() <= 2 {
+                false
+            } else if result.column_totals.len() <= 2 {
+                true
+            } else {
+                result.row_totals[row] <= result.column_totals[column]
+            };
+            result = if merge_row {
+                result.merge_rows(merge_neighbour(&result.row_totals, row))
+            } else {
+                result.merge_columns(merge_neighbour(&result.column_totals, column))
+            };
+        }
+
+        result
+    }
+
+    /// runs fisher's exact test (freeman-halton for tables bigger than 2×2), keeping its
+    /// p-value or why it couldn't be run, or clears its result if `run` is false
+    pub fn set_exact_test(&mut self, run: bool) {
+        (self.exact_p_value, self.exact_error) = match run {
+            true => match exact::fisher_exact(&self.observed) {
+                Ok(p_value) => (Some(p_value), None),
+                Err(error) => (None, Some(error)),
+            },
+            false => (None, None),
+        };
+    }
+
+    /// whether fisher's exact test has been run on the table, whether or not it could be
+    pub fn exact_test_tried(&self) -> bool {
+        self.exact_p_value.is_some() || self.exact_error.is_some()
+    }
+
+    /// (O - E)² / E for each cell, which sum to the statistic
+    pub fn contributions(&self) -> Vec<Vec<f64>> {
+        self.observed.iter().zip(self.expected.iter())
+            .map(|(o_row, e_row)| o_row.iter().zip(e_row.iter()).map(|(o, e)| (o - e).powi(2) / e).collect())
+            .collect()
+    }
+
+    /// turns yates' continuity correction on or off.
+    /// it's only ever applied with 1 degree of freedom (a 2×2 table)
+    pub fn set_yates_correction(&mut self, apply: bool) {
+        self.yates_correction = apply;
+        self.yates_statistic = if apply && self.df == 1 {
+            Some(self.observed.iter().zip(self.expected.iter()).map(|(o_row, e_row)| yates_statistic(o_row, e_row)).sum())
+        } else {
+            None
+        };
+    }
+
+    /// the statistic the test is decided on: the corrected one if yates' correction is applied
+    pub fn test_statistic(&self) -> f64 {
+        self.yates_statistic.unwrap_or(self.statistic)
+    }
+
+    pub fn p_value(&self) -> f64 {
+        ChiSquared::new(self.df).sf(self.test_statistic())
+    }
+
+    /// the value the statistic must exceed to reject H0 at the given significance (as a proportion)
+    pub fn critical_value(&self, significance: f64) -> f64 {
+        ChiSquared::new(self.df).critical_value(significance)
+    }
+}
+
+/// sum of (O - E)² / E over every class
+pub fn chi_squared_statistic(observed: &[f64], expected: &[f64]) -> f64 {
+    observed.iter().zip(expected.iter()).map(|(o, e )| (o - e).powi(2) / e).sum::<f64>()
+}
+
+/// sum of (|O - E| - 0.5)² / E over every class, yates' continuity correction.
+/// |O - E| is never corrected below 0, so the correction can't overshoot
+pub fn yates_statistic(observed: &[f64], expected: &[f64]) -> f64 {
+    observed.iter().zip(expected.iter()).map(|(o, e)| ((o - e).abs() - 0.5).max(0.).powi(2) / e).sum::<f64>()
+}
+
+/// index of the smallest value (the first, if there's a tie)
+fn lowest_index(values: &[f64]) -> usize {
+    let mut lowest = 0;
+    for i in 1..values.len() {
+        if values[i] < values[lowest] {
+            lowest = i;
+        }
+    }
+
+    lowest
+}
+
+/// the first of the adjacent pair to merge `i` with: whichever neighbour has the smaller total
+fn merge_neighbour(totals: &[f64], i: usize) -> usize {
+    if i == 0 {
+        0
+    } else if i == totals.len()-1 || totals[i-1] <= totals[i+1] {
+        i-1
+    } else {
+        i
+    }
+}
+
+/// the labels with `first` and the label after it joined as "a+b"
+fn merge_labels(labels: &[String], first: usize) -> Vec<String> {
+    let mut labels = labels.to_vec();
+    let label = labels.remove(first+1);
+    labels[first] = format!("{}+{label}", labels[first]);
+    labels
+}
+
+/// the degrees of freedom `classes` leave once `lost` are taken for the total and any estimated
+/// parameters, which must leave at least 1 to test the statistic against
+fn degrees_of_freedom(classes: usize, lost: usize) -> Result<usize, String> {
+    if classes <= lost {
+        return Err(format!(
+            "there {} only {classes} class{}, which leaves no degrees of freedom once {lost} {} taken for the total and any estimated parameters",
+            if classes == 1 {"is"} else {"are"},
+            if classes == 1 {""} else {"es"},
+            if lost == 1 {"is"} else {"are"}
+        ));
+    }
+
+    Ok(classes - lost)
+}
+
+/// the rule the expected frequencies must meet once they're grouped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MinimumExpected {
+    /// every expected frequency is at least this
+    AtLeast(f64),
+    /// cochran's rule: every expected frequency is at least 1, and no more than 20% are below 5
+    Cochran,
+}
+
+impl MinimumExpected {
+    pub fn is_met(&self, expecteds: &[f64]) -> bool {
+        match *self {
+            MinimumExpected::AtLeast(minimum) => expecteds.iter().all(|e| *e >= minimum),
+            MinimumExpected::Cochran => {
+                let below_five = expecteds.iter().filter(|e| **e < 5.).count();
+                expecteds.iter().all(|e| *e >= 1.) && below_five * 5 <= expecteds.len()
+            }
+        }
+    }
+}
+
+impl Default for MinimumExpected {
+    fn default() -> MinimumExpected {
+        MinimumExpected::AtLeast(5.)
+    }
+}
+
+impl fmt::Display for MinimumExpected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MinimumExpected::AtLeast(minimum) => write!(f, "every expected frequency at least {minimum}"),
+            MinimumExpected::Cochran => write!(f, "Cochran's rule"),
+        }
+    }
+}
+
+/// which of the original classes (such as the values of X) make up each class of a test
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grouping {
+    /// the first and last original class in each group
+    pub groups: Vec<(usize, usize)>,
+}
+
+impl Grouping {
+    /// every class in a group of its own
+    pub fn ungrouped(classes: usize) -> Grouping {
+        Grouping { groups: (0..classes).map(|i| (i, i)).collect() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// adds up the values of the original classes in each group
+    pub fn group(&self, values: &[f64]) -> Vec<f64> {
+        self.groups.iter().map(|(first, last)| values[*first..*last+1].iter().sum()).collect()
+    }
+
+    /// joins group `first` with the group after it
+    pub fn merge(&mut self, first: usize) {
+        let (_, last) = self.groups.remove(first+1);
+        self.groups[first].1 = last;
+    }
+
+    /// labels for each group from the values of the original classes, where the first class
+    /// is `first_value`: "<= b" for a grouped lower tail, ">= a" for a grouped upper tail and
+    /// "a-b" for grouped classes in between. if `open_last`, the last class already takes in
+    /// every value above it, so is always labelled ">= a"
+    pub fn labels(&self, first_value: usize, open_last: bool) -> Vec<String> {
+        let classes = self.groups.last().map_or(0, |(_, last)| last+1);
+        self.groups.iter()
+            .map(|(first, last)| {
+                let (first_value, last_value) = (first + first_value, last + first_value);
+                if *last == classes-1 && open_last {
+                    format!(">= {first_value}")
+                } else if first == last {
+                    first_value.to_string()
+                } else if *first == 0 && *last < classes-1 {
+                    format!("<= {last_value}")
+                } else if *last == classes-1 && *first > 0 {
+                    format!(">= {first_value}")
+                } else {
+                    format!("{first_value}-{last_value}")
+                }
+            })
+            .collect()
+    }
+}
+
+/// groups adjacent classes until the expecteds meet `minimum`, each time merging the class with
+/// the lowest expected frequency into its smaller neighbour, so low tails and low classes
+/// in between are both grouped. classes expected to be empty are always grouped, whatever the minimum
+pub fn group_expecteds(expecteds: &[f64], minimum: MinimumExpected) -> Result<Grouping, String> {
+    if expecteds.is_empty() {
+        return Err(String::from("there are no expected frequencies to group"));
+    }
+    if expecteds.iter().any(|e| !e.is_finite() || *e < 0.) {
+        return Err(String::from("expected frequencies must be finite and not negative"));
+    }
+
+    let mut grouping = Grouping::ungrouped(expecteds.len());
+    let mut grouped = expecteds.to_vec();
+    // (O - E)² / E can't be worked out for a class with nothing expected in it
+    while !minimum.is_met(&grouped) || grouped.iter().any(|e| *e <= 0.) {
+        if grouped.len() == 1 {
+            return Err(format!(
+                "the expected frequencies only add up to {}, so no grouping meets {minimum}",
+                grouped[0]
+            ));
+        }
+        let lowest = lowest_index(&grouped);
+        grouping.merge(merge_neighbour(&grouped, lowest));
+        grouped = grouping.group(expecteds);
+    }
+    if grouping.len() < 2 && expecteds.len() > 1 {
+        return Err(format!("every class had to be grouped together to meet {minimum}, leaving nothing to test"));
+    }
+
+    Ok(grouping)
+}
+
+fn create_binomial_expecteds(n: usize, p: f64, freq_sum: f64) -> Vec<f64> {
+    let mut expecteds = Vec::with_capacity(n+1);
+    for i in 0..n+1 {
+        expecteds.push(distribution::binomial_pmf(n as u64, p, i as u64) * freq_sum);
+    }
+
+    expecteds
+}
+
+fn create_poisson_expecteds(mean: f64, freq_sum: f64, columns: usize) -> Vec<f64> {
+    let mut expecteds = Vec::with_capacity(columns);
+    for i in 0..columns-1 { // -1 as last is >=
+        expecteds.push(freq_sum * distribution::poisson_pmf(mean, i as u64))
+    }
+    expecteds.push(freq_sum - expecteds.iter().sum::<f64>());
+
+    expecteds
+}
+
+fn create_geometric_expecteds(p: f64, freq_sum: f64, columns: usize) -> Vec<f64> {
+    let mut expecteds = Vec::with_capacity(columns);
+    for i in 0..columns-1 { // -1 as last is >=
+        expecteds.push(freq_sum * distribution::geometric_pmf(p, i as u64 + 1))
+    }
+    expecteds.push(freq_sum - expecteds.iter().sum::<f64>());
+
+    expecteds
+}
+
+/// reads a class interval written as "lower-upper" (or with "–" or " to " between them),
+/// such as "10-20" or "-5 to 0"
+pub fn parse_class_interval(label: &str) -> Result<(f64, f64), String> {
+    let label = label.trim();
+    let split = if let Some(split) = label.split_once(" to ") {
+        Some(split)
+    } else if let Some(split) = label.split_once('–') {
+        Some(split)
+    } else {
+        // the first '-' after the start of the lower bound, so it can be negative
+        label.char_indices()
+            .skip(1)
+            .find(|(i, ch)| *ch == '-' && !label[..*i].trim_end().ends_with(['-', 'e', 'E']))
+            .map(|(i, _)| (&label[..i], &label[i+1..]))
+    };
+
+    let interval = split.and_then(|(lower, upper)| Some((lower.trim().parse::<f64>().ok()?, upper.trim().parse::<f64>().ok()?)));
+    match interval {
+        Some((lower, upper)) if lower.is_finite() && upper.is_finite() && lower < upper => Ok((lower, upper)),
+        Some(_) => Err(format!("the class '{label}' must have a lower bound below its upper bound")),
+        None => Err(format!("'{label}' is not a class interval like 10-20")),
+    }
+}
+
+/// reads the label of a value of X from `first_value` up (and up to `last_value` if there is one),
+/// returning whether it's open ended (written as ">= x") and the value
+pub fn parse_value_label(label: &str, first_value: usize, last_value: Option<usize>) -> Result<(bool, usize), String> {
+    let (open, value) = match label.trim().strip_prefix(">=") {
+        Some(value) => (true, value.trim()),
+        None => (false, label.trim()),
+    };
+    let value = match value.parse::<usize>() {
+        Ok(value) if value >= first_value => value,
+        _ => return Err(format!("'{label}' is not a whole number value of X from {first_value} up")),
+    };
+    if let Some(last_value) = last_value {
+        if value > last_value {
+            return Err(format!("'{label}' is not a value of X from {first_value} to {last_value}"));
+        }
+        if open {
+            return Err(format!("'{label}' can't be open ended, as X only goes up to {last_value}"));
+        }
+    }
+
+    Ok((open, value))
+}
+
+/// the frequency of each value of X from `first_value` up, from observed frequencies labelled
+/// with their values. labels can be in any order and values left out have a frequency of 0.
+/// with a `last_value` there's a frequency for every value up to it, otherwise the
+/// largest label is the last value (and can be written as ">= x", as it's open ended).
+/// there can be at most [`MAX_VALUES`] values of X
+///
+/// # Panics
+/// if there are a different number of labels and observed frequencies
+pub fn value_frequencies(
+    labels: &[String],
+    observed: &[f64],
+    first_value: usize,
+    last_value: Option<usize>
+) -> Result<Vec<f64>, String> {
+    assert_eq!(labels.len(), observed.len(), "there must be a value for each observed frequency");
+
+    let mut values = Vec::with_capacity(labels.len());
+    for label in labels {
+        let (open, value) = parse_value_label(label, first_value, last_value)?;
+        if values.iter().any(|(_, other)| *other == value) {
+            return Err(format!("X = {value} is given more than once"));
+        }
+        values.push((open, value));
+    }
+
+    let largest = match values.iter().map(|(_, value)| *value).max() {
+        Some(largest) => largest,
+        None => return Err(String::from("there are no values of X")),
+    };
+    if values.iter().any(|(open, value)| *open && *value != largest) {
+        return Err(String::from("only the largest value of X can be open ended (>=)"));
+    }
+
+    let last_value = last_value.unwrap_or(largest);
+    if last_value - first_value >= MAX_VALUES {
+        return Err(format!(
+            "X = {last_value} is too large, as X can only take {MAX_VALUES} values from {first_value}"
+        ));
+    }
+
+    let mut frequencies = vec![0.; last_value + 1 - first_value];
+    for (i, (_, value)) in values.iter().enumerate() {
+        frequencies[value - first_value] = observed[i];
+    }
+
+    Ok(frequencies)
+}
+
+/// the boundaries between adjacent classes, halfway across any gap between them
+/// (so 10-19 and 20-29 meet at 19.5)
+fn class_boundaries(intervals: &[(f64, f64)]) -> Result<Vec<f64>, String> {
+    let mut boundaries = Vec::with_capacity(intervals.len().saturating_sub(1));
+    for i in 1..intervals.len() {
+        let (upper, lower) = (intervals[i-1].1, intervals[i].0);
+        if lower < upper {
+            return Err(String::from("class intervals must be in order and not overlap"));
+        }
+        boundaries.push((upper + lower) / 2.);
+    }
+
+    Ok(boundaries)
+}
+
+/// the mean and standard deviation of grouped data, taking each class at its midpoint
+fn grouped_mean_and_sd(intervals: &[(f64, f64)], observed: &[f64]) -> (f64, f64) {
+    let freq_sum: f64 = observed.iter().sum();
+    let midpoints: Vec<f64> = intervals.iter().map(|(lower, upper)| (lower + upper) / 2.).collect();
+
+    let mut sum_x_f = 0.;
+    for i in 0..observed.len() {
+        sum_x_f += midpoints[i] * observed[i];
+    }
+    let mean = sum_x_f / freq_sum;
+
+    let mut sum_squares = 0.;
+    for i in 0..observed.len() {
+        sum_squares += (midpoints[i] - mean).powi(2) * observed[i];
+    }
+
+    (mean, (sum_squares / (freq_sum - 1.)).sqrt())
+}
+
+/// the class interval of each label, checking there's one for each observed frequency
+/// and enough observations to fit a distribution to
+///
+/// # Panics
+/// if there are a different number of labels and observed frequencies
+fn class_intervals(labels: &[String], observed: &[f64]) -> Result<Vec<(f64, f64)>, String> {
+    assert_eq!(labels.len(), observed.len(), "there must be a class interval for each observed frequency");
+
+    if observed.iter().sum::<f64>() <= 1. {
+        return Err(String::from("there must be more than 1 observation"));
+    }
+    labels.iter().map(|label| parse_class_interval(label)).collect()
+}
+
+/// fits a continuous distribution with the given cdf to grouped data, taking the first class
+/// as everything below its upper boundary and the last as everything above its lower one
+fn interval_fit(
+    model: Model,
+    estimated: Vec<&'static str>,
+    labels: &[String],
+    intervals: &[(f64, f64)],
+    observed: &[f64],
+    cdf: impl Fn(f64) -> f64,
+    minimum: MinimumExpected
+) -> Result<GoodnessOfFit, String> {
+    let boundaries = class_boundaries(intervals)?;
+    let freq_sum: f64 = observed.iter().sum();
+
+    let mut expecteds = Vec::with_capacity(observed.len());
+    let mut below = 0.;
+    for boundary in boundaries.iter() {
+        let cdf = cdf(*boundary);
+        expecteds.push(freq_sum * (cdf - below));
+        below = cdf;
+    }
+    expecteds.push(freq_sum * (1. - below));
+
+    let mut result = grouped_fit(model, estimated, observed, &expecteds, minimum)?;
+    if let Some(grouping) = &result.grouping {
+        result.class_labels = grouped_interval_labels(grouping, labels, intervals);
+    }
+
+    Ok(result)
+}
+
+/// labels for grouped class intervals, running from the first interval's lower
+/// bound to the last one's upper bound
+fn grouped_interval_labels(grouping: &Grouping, labels: &[String], intervals: &[(f64, f64)]) -> Vec<String> {
+    grouping.groups.iter()
+        .map(|(first, last)| {
+            if first == last {
+                labels[*first].clone()
+            } else {
+                format!("{}-{}", intervals[*first].0, intervals[*last].1)
+            }
+        })
+        .collect()
+}
+
+fn create_negative_binomial_expecteds(r: f64, p: f64, freq_sum: f64, columns: usize) -> Vec<f64> {
+    let mut expecteds = Vec::with_capacity(columns);
+    for i in 0..columns-1 { // -1 as last is >=
+        expecteds.push(freq_sum * distribution::negative_binomial_pmf(r, p, i as u64))
+    }
+    expecteds.push(freq_sum - expecteds.iter().sum::<f64>());
+
+    expecteds
+}
+
+/// groups a fitted distribution's expecteds to meet `minimum` and tests the observeds against them
+fn grouped_fit(
+    model: Model,
+    estimated: Vec<&'static str>,
+    observed: &[f64],
+    expecteds: &[f64],
+    minimum: MinimumExpected
+) -> Result<GoodnessOfFit, String> {
+    let grouping = group_expecteds(expecteds, minimum)?;
+    let observed = grouping.group(observed);
+    let expected = grouping.group(expecteds);
+    // geometric values start from 1 trial
+    let first_value = if let Model::Geometric { .. } = model {1} else {0};
+    // counts with no upper limit have the rest of the distribution in their last class
+    let open_last = matches!(model, Model::Poisson { .. } | Model::Geometric { .. } | Model::NegativeBinomial { .. });
+
+    // one lost for the total, and one for each estimated parameter
+    let lost = 1 + estimated.len();
+    let df = match degrees_of_freedom(expected.len(), lost) {
+        Err(_) if expected.len() < expecteds.len() => return Err(format!(
+            "grouping the classes to meet {minimum} leaves only {} of them, which is too few to test \
+            once {lost} degrees of freedom are taken for the total and any estimated parameters",
+            expected.len()
+        )),
+        df => df?,
+    };
+
+    Ok(GoodnessOfFit {
+        model,
+        estimated,
+        class_labels: grouping.labels(first_value, open_last),
+        statistic: chi_squared_statistic(&observed, &expected),
+        yates_statistic: None,
+        yates_correction: false,
+        df,
+        observed,
+        expected,
+        grouping: Some(grouping),
+    })
+}
+
+/// tests observed frequencies against expected frequencies given for each class, of which
+/// there must be at least 2. yates' correction is applied if there are 2 classes (1 degree of freedom)
+///
+/// # Panics
+/// if there are a different number of observed and expected frequencies
+pub fn observed_expected(observed: &[f64], expected: &[f64]) -> Result<GoodnessOfFit, String> {
+    assert_eq!(observed.len(), expected.len(), "observed and expected lengths do not match");
+
+    let mut result = GoodnessOfFit {
+        model: Model::Given,
+        estimated: Vec::new(),
+        class_labels: (1..observed.len()+1).map(|i| i.to_string()).collect(),
+        observed: observed.to_vec(),
+        expected: expected.to_vec(),
+        grouping: None,
+        statistic: chi_squared_statistic(observed, expected),
+        yates_statistic: None,
+        yates_correction: true,
+        df: degrees_of_freedom(observed.len(), 1)?,
+    };
+    result.set_yates_correction(true);
+
+    Ok(result)
+}
+
+/// fits X ~ B(n, p), where `observed[x]` is the frequency of X = x,
+/// grouping classes until the expected frequencies meet `minimum`.
+/// n can be at most [`MAX_VALUES`] - 1, so X has at most [`MAX_VALUES`] values
+pub fn binomial(n: usize, p: BinomialP, observed: &[f64], minimum: MinimumExpected) -> Result<GoodnessOfFit, String> {
+    if n >= MAX_VALUES {
+        return Err(format!("n can be at most {}", MAX_VALUES - 1));
+    }
+    if observed.len() != n + 1 {
+        return Err(format!("there must be an observed frequency for each value 0 to {n} ({} values)", n + 1));
+    }
+
+    let freq_sum: f64 = observed.iter().sum();
+    if freq_sum <= 0. {
+        return Err(String::from("the observed frequencies add up to 0"));
+    }
+
+    let estimated = if let BinomialP::Estimate = p {vec!["p"]} else {Vec::new()};
+    let p = match p {
+        BinomialP::P(p) => p,
+        BinomialP::Estimate if n == 0 => return Err(String::from("p can't be estimated when n is 0")),
+        BinomialP::Estimate => {
+            let sum_x_f: f64 = observed.iter().enumerate().map(|(x, f)| x as f64 * f).sum();
+            sum_x_f / (freq_sum * n as f64)
+        }
+    };
+    if !(0. ..=1.).contains(&p) {
+        return Err(String::from("p must be between 0 and 1"));
+    }
+
+    let expecteds = create_binomial_expecteds(n, p, freq_sum);
+    grouped_fit(Model::Binomial { n, p }, estimated, observed, &expecteds, minimum)
+}
+
+/// fits X ~ Po(λ), where `observed[x]` is the frequency of X = x and the last observed
+/// frequency is for X >= x, grouping classes until the expected frequencies meet `minimum`
+pub fn poisson(mean: PoissonMean, observed: &[f64], minimum: MinimumExpected) -> Result<GoodnessOfFit, String> {
+    let freq_sum: f64 = observed.iter().sum();
+    if freq_sum <= 0. {
+        return Err(String::from("the observed frequencies add up to 0"));
+    }
+
+    let estimated = if let PoissonMean::Estimate = mean {vec!["lambda"]} else {Vec::new()};
+    let mean = match mean {
+        PoissonMean::Mean(m) => m,
+        PoissonMean::Estimate => {
+            let sum_r_f: f64 = observed.iter().enumerate().map(|(r, f)| r as f64 * f).sum();
+            sum_r_f / freq_sum
+        }
+    };
+    if !(mean >= 0. && mean.is_finite()) {
+        return Err(String::from("λ must be 0 or more"));
+    }
+
+    let expecteds = create_poisson_expecteds(mean, freq_sum, observed.len());
+    grouped_fit(Model::Poisson { mean }, estimated, observed, &expecteds, minimum)
+}
+
+/// fits X ~ Geo(p), where `observed[i]` is the frequency of X = i + 1 trials and the last
+/// observed frequency is for X >= i + 1, grouping classes until the expected frequencies meet `minimum`
+pub fn geometric(p: GeometricP, observed: &[f64], minimum: MinimumExpected) -> Result<GoodnessOfFit, String> {
+    let freq_sum: f64 = observed.iter().sum();
+    if freq_sum <= 0. {
+        return Err(String::from("the observed frequencies add up to 0"));
+    }
+
+    let estimated = if let GeometricP::Estimate = p {vec!["p"]} else {Vec::new()};
+    let p = match p {
+        GeometricP::P(p) => p,
+        GeometricP::Estimate => {
+            // p = 1 / mean number of trials
+            let sum_x_f: f64 = observed.iter().enumerate().map(|(i, f)| (i + 1) as f64 * f).sum();
+            freq_sum / sum_x_f
+        }
+    };
+    if !(p > 0. && p <= 1.) {
+        return Err(String::from("p must be greater than 0 and at most 1"));
+    }
+
+    let expecteds = create_geometric_expecteds(p, freq_sum, observed.len());
+    grouped_fit(Model::Geometric { p }, estimated, observed, &expecteds, minimum)
+}
+
+/// fits X ~ NB(r, p), the number of failures before the rth success, where `observed[x]`
+/// is the frequency of X = x and the last observed frequency is for X >= x, grouping classes
+/// until the expected frequencies meet `minimum`. r and p are estimated by the method of
+/// moments (from the mean, and the variance if both are), which needs the variance to be
+/// above the mean
+pub fn negative_binomial(
+    r: NegativeBinomialR,
+    p: NegativeBinomialP,
+    observed: &[f64],
+    minimum: MinimumExpected
+) -> Result<GoodnessOfFit, String> {
+    let freq_sum: f64 = observed.iter().sum();
+    if freq_sum <= 1. {
+        return Err(String::from("there must be more than 1 observation"));
+    }
+
+    let sum_x_f: f64 = observed.iter().enumerate().map(|(x, f)| x as f64 * f).sum();
+    let mean = sum_x_f / freq_sum;
+    let sum_squares: f64 = observed.iter().enumerate().map(|(x, f)| (x as f64 - mean).powi(2) * f).sum();
+    let variance = sum_squares / (freq_sum - 1.);
+
+    // mean = r(1 - p) / p and variance = r(1 - p) / p²
+    let (r, p, estimated) = match (r, p) {
+        (NegativeBinomialR::R(r), NegativeBinomialP::P(p)) => (r, p, Vec::new()),
+        (NegativeBinomialR::R(r), NegativeBinomialP::Estimate) => (r, r / (r + mean), vec!["p"]),
+        (NegativeBinomialR::Estimate, NegativeBinomialP::P(p)) => (mean * p / (1. - p), p, vec!["r"]),
+        (NegativeBinomialR::Estimate, NegativeBinomialP::Estimate) => {
+            if variance <= mean {
+                return Err(format!(
+                    "the variance ({variance}) isn't above the mean ({mean}), so the data isn't overdispersed and r can't be estimated"
+                ));
+            }
+            (mean * mean / (variance - mean), mean / variance, vec!["r", "p"])
+        },
+    };
+    if !(r > 0. && r.is_finite()) {
+        return Err(String::from("r must be positive"));
+    }
+    if !(p > 0. && p <= 1.) {
+        return Err(String::from("p must be greater than 0 and at most 1"));
+    }
+
+    let expecteds = create_negative_binomial_expecteds(r, p, freq_sum, observed.len());
+    grouped_fit(Model::NegativeBinomial { r, p }, estimated, observed, &expecteds, minimum)
+}
+
+/// fits X ~ N(μ, σ²) to grouped continuous data, where `observed[i]` is the frequency of the
+/// class `labels[i]`, a class interval like "10-20". the first and last classes are taken as
+/// open ended, and classes are grouped until the expected frequencies meet `minimum`.
+/// estimates of μ and σ take each class at its midpoint
+///
+/// # Panics
+/// if there are a different number of labels and observed frequencies
+pub fn normal(
+    mean: NormalMean,
+    sd: NormalSd,
+    labels: &[String],
+    observed: &[f64],
+    minimum: MinimumExpected
+) -> Result<GoodnessOfFit, String> {
+    let intervals = class_intervals(labels, observed)?;
+
+    let (estimated_mean, estimated_sd) = grouped_mean_and_sd(&intervals, observed);
+    let mut estimated = Vec::new();
+    let mean = match mean {
+        NormalMean::Mean(mean) => mean,
+        NormalMean::Estimate => {
+            estimated.push("mean");
+            estimated_mean
+        }
+    };
+    let sd = match sd {
+        NormalSd::Sd(sd) => sd,
+        NormalSd::Estimate => {
+            estimated.push("sd");
+            estimated_sd
+        }
+    };
+    if !(sd > 0. && sd.is_finite()) {
+        return Err(String::from("the standard deviation must be positive"));
+    }
+
+    let cdf = |x| distribution::normal_cdf(mean, sd, x);
+    interval_fit(Model::Normal { mean, sd }, estimated, labels, &intervals, observed, cdf, minimum)
+}
+
+/// fits X ~ U(a, b) to grouped continuous data like [`normal`] does, with a and b taken as the
+/// lower bound of the first class and the upper bound of the last if they aren't given
+///
+/// # Panics
+/// if there are a different number of labels and observed frequencies
+pub fn uniform(range: UniformRange, labels: &[String], observed: &[f64], minimum: MinimumExpected) -> Result<GoodnessOfFit, String> {
+    let intervals = class_intervals(labels, observed)?;
+
+    let (a, b) = match range {
+        UniformRange::Range(a, b) => (a, b),
+        UniformRange::Classes => (intervals[0].0, intervals[intervals.len()-1].1),
+    };
+    if a >= b || a.is_nan() || b.is_nan() {
+        return Err(String::from("the lower end of the range must be below the upper end"));
+    }
+
+    let cdf = |x| distribution::uniform_cdf(a, b, x);
+    // a and b come from the classes rather than the frequencies, so they don't cost any degrees of freedom
+    interval_fit(Model::Uniform { a, b }, Vec::new(), labels, &intervals, observed, cdf, minimum)
+}
+
+/// fits X ~ Exp(λ) to grouped continuous data like [`normal`] does, estimating λ as
+/// 1 / the mean (taking each class at its midpoint)
+///
+/// # Panics
+/// if there are a different number of labels and observed frequencies
+pub fn exponential(rate: ExponentialRate, labels: &[String], observed: &[f64], minimum: MinimumExpected) -> Result<GoodnessOfFit, String> {
+    let intervals = class_intervals(labels, observed)?;
+
+    let estimated = if let ExponentialRate::Estimate = rate {vec!["lambda"]} else {Vec::new()};
+    let rate = match rate {
+        ExponentialRate::Rate(rate) => rate,
+        ExponentialRate::Estimate => 1. / grouped_mean_and_sd(&intervals, observed).0,
+    };
+    if !(rate > 0. && rate.is_finite()) {
+        return Err(String::from("the rate must be positive"));
+    }
+
+    let cdf = |x| distribution::exponential_cdf(rate, x);
+    interval_fit(Model::Exponential { rate }, estimated, labels, &intervals, observed, cdf, minimum)
+}
+
+/// tests for association between the rows and columns of a table of observed frequencies,
+/// which needs at least 2 rows and 2 columns, none of them with a total of 0. yates' correction is applied to 2×2 tables
+/// (1 degree of freedom), and fisher's exact test is run if any expected frequency is below 5
+/// (keeping why it couldn't be if the table is too large for it)
+///
+/// # Panics
+/// if the rows aren't all the same length
+pub fn contingency_table(observed: &[Vec<f64>]) -> Result<ContingencyTest, String> {
+    let mut result = contingency_test(observed)?;
+    if result.has_low_expecteds() {
+        result.set_exact_test(true);
+    }
+
+    Ok(result)
+}
+
+/// [`contingency_table`] without fisher's exact test, so it can be run once with
+/// [`ContingencyTest::set_exact_test`] after any merging, rather than on every table along the way
+///
+/// # Panics
+/// if the rows aren't all the same length
+pub fn contingency_test(observed: &[Vec<f64>]) -> Result<ContingencyTest, String> {
+    let rows = observed.len();
+    let columns = observed.first().map_or(0, |row| row.len());
+    assert!(observed.iter().all(|row| row.len() == columns), "row lengths do not match");
+    if rows < 2 || columns < 2 {
+        return Err(String::from("a contingency table needs at least 2 rows and 2 columns"));
+    }
+
+    // make totals
+    let mut column_totals = vec![0.; columns];
+    let mut row_totals = vec![0.; rows];
+    for i in 0..rows {
+        row_totals[i] = observed[i].iter().sum();
+        for j in 0..columns {
+            column_totals[j] += observed[i][j];
+        }
+    }
+    let grand_total = row_totals.iter().sum::<f64>();
+    // an empty row or column would have expected frequencies of 0 to divide by
+    if let Some(i) = row_totals.iter().position(|total| *total == 0.) {
+        return Err(format!("row {} has a total of 0", i+1));
+    }
+    if let Some(j) = column_totals.iter().position(|total| *total == 0.) {
+        return Err(format!("column {} has a total of 0", j+1));
+    }
+
+    let mut statistic: f64 = 0.;
+    let mut expected = vec![vec![0.; columns]; rows];
+    for i in 0..rows {
+        for j in 0..columns {
+            let e = row_totals[i]*column_totals[j] / grand_total;
+            statistic += (observed[i][j] - e).powi(2) / e;
+            expected[i][j] = e;
+        }
+    }
+
+    let mut result = ContingencyTest {
+        row_labels: (1..rows+1).map(|i| i.to_string()).collect(),
+        column_labels: (1..columns+1).map(|i| i.to_string()).collect(),
+        observed: observed.to_vec(),
+        expected,
+        row_totals,
+        column_totals,
+        grand_total,
+        statistic,
+        yates_statistic: None,
+        yates_correction: true,
+        df: (rows - 1) * (columns - 1),
+        exact_p_value: None,
+        exact_error: None,
+    };
+    result.set_yates_correction(true);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_binomial_and_poisson_fits() {
+        let observed: Vec<f64> = (0..101).map(|x| (distribution::binomial_pmf(100, 0.4, x) * 1000.).round()).collect();
+        let result = binomial(100, BinomialP::Estimate, &observed, MinimumExpected::default()).unwrap();
+        assert!(result.statistic.is_finite());
+        assert!(result.expected.iter().all(|e| *e >= 5.));
+
+        let observed: Vec<f64> = (0..40).map(|x| (distribution::poisson_pmf(20., x) * 1000.).round()).collect();
+        let result = poisson(PoissonMean::Mean(20.), &observed, MinimumExpected::default()).unwrap();
+        assert!(result.statistic.is_finite());
+        assert!(result.p_value() > 0.05);
+    }
+
+    #[test]
+    fn grouping_tails_and_interior_classes() {
+        let grouping = group_expecteds(&[1., 2., 3., 10., 2., 9., 4., 1.], MinimumExpected::default()).unwrap();
+        assert_eq!(grouping.groups, vec![(0, 2), (3, 3), (4, 5), (6, 7)]);
+        assert_eq!(grouping.labels(0, false), vec!["<= 2", "3", "4-5", ">= 6"]);
+        let ungrouped = Grouping::ungrouped(3);
+        assert_eq!(ungrouped.labels(1, false), vec!["1", "2", "3"]);
+        assert_eq!(ungrouped.labels(1, true), vec!["1", "2", ">= 3"]);
+        assert_eq!(grouping.group(&[1., 2., 3., 10., 2., 9., 4., 1.]), vec![6., 10., 11., 5.]);
+
+        // cochran's rule allows one of these 5 classes below 5, but none below 1
+        let grouping = group_expecteds(&[0.5, 3., 8., 9., 7., 6.], MinimumExpected::Cochran).unwrap();
+        assert_eq!(grouping.groups, vec![(0, 1), (2, 2), (3, 3), (4, 4), (5, 5)]);
+
+        assert!(group_expecteds(&[1., 2., 1.], MinimumExpected::default()).is_err());
+        assert!(group_expecteds(&[], MinimumExpected::default()).is_err());
+
+        // empty classes are grouped even without a minimum
+        let grouping = group_expecteds(&[0., 4., 6., 0.], MinimumExpected::AtLeast(0.)).unwrap();
+        assert_eq!(grouping.groups, vec![(0, 1), (2, 3)]);
+        assert!(poisson(PoissonMean::Estimate, &[0., 0., 0.], MinimumExpected::default()).is_err());
+    }
+
+    #[test]
+    fn geometric_fit() {
+        let result = geometric(GeometricP::Estimate, &[40., 25., 15., 10., 10.], MinimumExpected::default()).unwrap();
+        assert_eq!(result.model, Model::Geometric { p: 100. / 225. });
+        assert_eq!(result.class_labels, vec!["1", "2", "3", "4", ">= 5"]);
+        assert!((result.expected.iter().sum::<f64>() - 100.).abs() < 1e-9);
+        assert_eq!(result.df, 3);
+    }
+
+    #[test]
+    fn no_degrees_of_freedom_is_an_error() {
+        // grouping to 2 classes with p estimated leaves nothing to test a near perfect fit against
+        let error = binomial(3, BinomialP::Estimate, &[2., 10., 10., 2.], MinimumExpected::default()).unwrap_err();
+        assert!(error.starts_with("grouping the classes to meet every expected frequency at least 5 leaves only 2 of them"));
+        // which a lower minimum doesn't
+        assert_eq!(binomial(3, BinomialP::Estimate, &[2., 10., 10., 2.], MinimumExpected::AtLeast(1.)).unwrap().df, 2);
+        assert_eq!(binomial(3, BinomialP::P(0.5), &[2., 10., 10., 2.], MinimumExpected::default()).unwrap().df, 1);
+        assert!(binomial(usize::MAX, BinomialP::P(0.5), &[2., 10.], MinimumExpected::default()).is_err());
+        assert!(binomial(2, BinomialP::P(0.5), &[2., 10.], MinimumExpected::default()).is_err());
+
+        assert!(observed_expected(&[10.], &[10.]).is_err());
+        assert!(contingency_table(&[vec![10., 20., 30.]]).is_err());
+        assert!(contingency_table(&[vec![10.], vec![20.]]).is_err());
+
+        // merging stops at 1 degree of freedom
+        let result = observed_expected(&[3., 2.], &[4., 1.]).unwrap().merge_low_expecteds();
+        assert_eq!(result.df, 1);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let minimum = MinimumExpected::default();
+        assert_eq!(
+            binomial(3, BinomialP::P(1.5), &[2., 10., 10., 2.], minimum).unwrap_err(),
+            "p must be between 0 and 1"
+        );
+        assert_eq!(binomial(0, BinomialP::Estimate, &[10.], minimum).unwrap_err(), "p can't be estimated when n is 0");
+        assert_eq!(poisson(PoissonMean::Mean(-1.), &[10., 5., 2.], minimum).unwrap_err(), "λ must be 0 or more");
+        assert_eq!(
+            geometric(GeometricP::P(1.5), &[10., 5., 2.], minimum).unwrap_err(),
+            "p must be greater than 0 and at most 1"
+        );
+        assert!(geometric(GeometricP::P(0.), &[10., 5., 2.], minimum).is_err());
+    }
+
+    #[test]
+    fn empty_rows_and_columns_are_errors() {
+        assert_eq!(contingency_table(&[vec![10., 20.], vec![0., 0.], vec![5., 6.]]), Err(String::from("row 2 has a total of 0")));
+        assert_eq!(contingency_table(&[vec![10., 0., 20.], vec![5., 0., 6.]]), Err(String::from("column 2 has a total of 0")));
+    }
+
+    #[test]
+    fn class_intervals() {
+        assert_eq!(parse_class_interval("10-20"), Ok((10., 20.)));
+        assert_eq!(parse_class_interval("-10--5"), Ok((-10., -5.)));
+        assert_eq!(parse_class_interval("1e-3 to 2"), Ok((0.001, 2.)));
+        assert!(parse_class_interval("20-10").is_err());
+        assert!(parse_class_interval("ten").is_err());
+    }
+
+    #[test]
+    fn normal_fit() {
+        let labels: Vec<String> = ["0-10", "10-20", "20-30", "30-40", "40-50", "50-60"].iter().map(|l| l.to_string()).collect();
+        let observed = [3., 14., 33., 31., 15., 4.];
+        let result = normal(NormalMean::Estimate, NormalSd::Estimate, &labels, &observed, MinimumExpected::default()).unwrap();
+        assert_eq!(result.estimated_parameters(), 2);
+        assert!((result.expected.iter().sum::<f64>() - 100.).abs() < 1e-9);
+        assert!(result.expected.iter().all(|e| *e >= 5.));
+        assert_eq!(result.class_labels[0], "0-20");
+        assert_eq!(result.df, result.expected.len() - 3);
+
+        assert!(normal(NormalMean::Mean(30.), NormalSd::Sd(10.), &labels[..2], &[5., 5.], MinimumExpected::AtLeast(0.)).is_ok());
+        assert!(normal(NormalMean::Estimate, NormalSd::Estimate, &["10-20".to_string(), "5-15".to_string()], &[5., 5.], MinimumExpected::default()).is_err());
+    }
+
+    #[test]
+    fn uniform_and_exponential_fits() {
+        let labels: Vec<String> = ["0-10", "10-20", "20-30", "30-40"].iter().map(|l| l.to_string()).collect();
+        let result = uniform(UniformRange::Classes, &labels, &[24., 26., 22., 28.], MinimumExpected::default()).unwrap();
+        assert_eq!(result.model, Model::Uniform { a: 0., b: 40. });
+        assert_eq!(result.expected, vec![25., 25., 25., 25.]);
+        assert_eq!(result.df, 3);
+
+        let result = exponential(ExponentialRate::Estimate, &labels, &[50., 25., 15., 10.], MinimumExpected::default()).unwrap();
+        // the mean of the midpoints is 13.5
+        assert_eq!(result.model, Model::Exponential { rate: 1. / 13.5 });
+        assert!((result.expected[0] - 100. * (1. - (-10_f64 / 13.5).exp())).abs() < 1e-9);
+        assert_eq!(result.df, 2);
+    }
+
+    #[test]
+    fn expecteds_from_ratios_and_proportions() {
+        let observed = [315., 108., 101., 32.];
+        let expected = ExpectedFrequencies::Ratio(parse_ratio("9:3:3:1").unwrap()).expecteds(&observed).unwrap();
+        assert_eq!(expected, vec![312.75, 104.25, 104.25, 34.75]);
+        assert_eq!(ExpectedFrequencies::Uniform.expecteds(&observed).unwrap(), vec![139.; 4]);
+
+        let proportions = parse_proportions("0.5, 0.25, 0.25").unwrap();
+        assert_eq!(ExpectedFrequencies::Proportions(proportions.clone()).expecteds(&[10., 6., 4.]).unwrap(), vec![10., 5., 5.]);
+        assert!(ExpectedFrequencies::Proportions(proportions).expecteds(&observed).is_err());
+        assert!(parse_proportions("0.5,0.2").is_err());
+        assert!(parse_ratio("9:0:1").is_err());
+    }
+
+    #[test]
+    fn negative_binomial_fit() {
+        let observed = [70., 38., 22., 14., 9., 7., 5., 10.];
+        let result = negative_binomial(NegativeBinomialR::Estimate, NegativeBinomialP::Estimate, &observed, MinimumExpected::default()).unwrap();
+        assert_eq!(result.estimated_parameters(), 2);
+        assert!((result.expected.iter().sum::<f64>() - 175.).abs() < 1e-9);
+        assert!(result.class_labels.last().unwrap().starts_with(">= "));
+
+        // poisson-like data isn't overdispersed
+        let observed = [10., 20., 20., 10.];
+        assert!(negative_binomial(NegativeBinomialR::Estimate, NegativeBinomialP::Estimate, &observed, MinimumExpected::default()).is_err());
+        assert!(negative_binomial(NegativeBinomialR::R(2.), NegativeBinomialP::Estimate, &observed, MinimumExpected::default()).is_ok());
+    }
+
+    #[test]
+    fn frequencies_from_labelled_values() {
+        let labels: Vec<String> = ["2", "0", ">= 4"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(value_frequencies(&labels, &[5., 7., 3.], 0, None), Ok(vec![7., 0., 5., 0., 3.]));
+        assert!(value_frequencies(&labels, &[5., 7., 3.], 0, Some(4)).is_err());
+
+        let labels: Vec<String> = ["3", "1"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(value_frequencies(&labels, &[2., 8.], 0, Some(3)), Ok(vec![0., 8., 0., 2.]));
+        assert_eq!(value_frequencies(&labels, &[2., 8.], 1, None), Ok(vec![8., 0., 2.]));
+        assert!(value_frequencies(&labels, &[2., 8.], 0, Some(2)).is_err());
+
+        assert_eq!(parse_value_label(" >= 4", 0, None), Ok((true, 4)));
+        assert!(parse_value_label("0", 1, None).is_err());
+        assert!(parse_value_label("x", 0, None).is_err());
+        assert_eq!(
+            parse_value_label(">= 2", 0, Some(3)),
+            Err(String::from("'>= 2' can't be open ended, as X only goes up to 3"))
+        );
+        assert!(parse_value_label("4", 0, Some(3)).is_err());
+
+        let duplicates: Vec<String> = ["1", "1"].iter().map(|l| l.to_string()).collect();
+        assert!(value_frequencies(&duplicates, &[2., 8.], 0, None).is_err());
+
+        let huge: Vec<String> = ["0", "99999999999"].iter().map(|l| l.to_string()).collect();
+        assert!(value_frequencies(&huge, &[2., 8.], 0, None).is_err());
+    }
+
+    #[test]
+    fn counts_and_weighted_frequencies() {
+        assert_eq!(Frequencies::Counts.parse(" 12 "), Ok(12.));
+        assert!(Frequencies::Counts.parse("12.5").is_err());
+        assert_eq!(Frequencies::Weighted.parse("12.5"), Ok(12.5));
+        assert!(Frequencies::Weighted.parse("-1").is_err());
+        assert!(Frequencies::Weighted.parse("many").is_err());
+
+        let result = poisson(PoissonMean::Mean(1.), &[36.8, 36.8, 18.4, 8.], MinimumExpected::default()).unwrap();
+        assert_eq!(result.observed, vec![36.8, 36.8, 18.4, 8.]);
+    }
+
+    #[test]
+    fn observed_expected_statistic() {
+        let result = observed_expected(&[10., 12., 8.], &[10., 10., 10.]).unwrap();
+        assert!((result.statistic - 0.8).abs() < 1e-12);
+        assert_eq!(result.df, 2);
+    }
+
+    #[test]
+    fn contingency_table_expecteds() {
+        let result = contingency_table(&[vec![10., 20.], vec![30., 40.]]).unwrap();
+        assert_eq!(result.expected, vec![vec![12., 18.], vec![28., 42.]]);
+        assert_eq!(result.df, 1);
+    }
+
+    #[test]
+    fn yates_correction_for_one_degree_of_freedom() {
+        let mut result = contingency_table(&[vec![10., 20.], vec![30., 40.]]).unwrap();
+        // each |O - E| is 2, corrected to 1.5
+        let corrected = 1.5_f64.powi(2) * (1./12. + 1./18. + 1./28. + 1./42.);
+        assert!((result.yates_statistic.unwrap() - corrected).abs() < 1e-12);
+        assert_eq!(result.test_statistic(), result.yates_statistic.unwrap());
+
+        result.set_yates_correction(false);
+        assert_eq!(result.test_statistic(), result.statistic);
+
+        let result = observed_expected(&[10., 12., 8.], &[10., 10., 10.]).unwrap();
+        assert_eq!(result.yates_statistic, None);
+    }
+
+    #[test]
+    fn exact_test_runs_for_low_expecteds() {
+        let result = contingency_table(&[vec![8., 2.], vec![1., 5.]]).unwrap();
+        assert!(result.has_low_expecteds());
+        assert!(result.exact_p_value.is_some());
+
+        let result = contingency_table(&[vec![10., 20.], vec![30., 40.]]).unwrap();
+        assert_eq!(result.exact_p_value, None);
+
+        let observed: Vec<Vec<f64>> = (0..4).map(|i| (0..8).map(|j| ((i + j) % 3 + 1) as f64).collect()).collect();
+        let result = contingency_table(&observed).unwrap();
+        assert_eq!(result.exact_p_value, None);
+        assert_eq!(result.exact_error, Some(String::from("the table is too large for the exact test")));
+
+        // merging leaves running it to the caller, once the table is final
+        let result = contingency_test(&observed).unwrap();
+        assert!(!result.exact_test_tried());
+        let mut result = result.merge_low_expecteds();
+        assert!(!result.exact_test_tried());
+        result.set_exact_test(true);
+        assert!(result.exact_test_tried());
+    }
+
+    #[test]
+    fn merging_low_expecteds() {
+        let result = observed_expected(&[20., 15., 3., 2.], &[18., 14., 4., 4.]).unwrap().merge_low_expecteds();
+        assert_eq!(result.class_labels, vec!["1", "2", "3+4"]);
+        assert_eq!(result.expected, vec![18., 14., 8.]);
+        assert_eq!(result.df, 2);
+
+        let result = contingency_table(&[vec![20., 25., 2.], vec![30., 20., 3.]]).unwrap();
+        assert_eq!(result.low_expected_cells(), vec![(0, 2), (1, 2)]);
+        let merged = result.merge_low_expecteds();
+        assert_eq!(merged.column_labels, vec!["1", "2+3"]);
+        assert_eq!(merged.observed, vec![vec![20., 27.], vec![30., 23.]]);
+        assert!(!merged.has_low_expecteds());
+        assert_eq!(merged.df, 1);
+    }
+
+    #[test]
+    fn merging_keeps_the_yates_choice() {
+        let mut result = observed_expected(&[20., 15., 5.], &[18., 14., 8.]).unwrap();
+        assert!(result.merge_classes(1).yates_statistic.is_some());
+        result.set_yates_correction(false);
+        assert_eq!(result.merge_classes(1).yates_statistic, None);
+
+        let mut result = contingency_test(&[vec![20., 25., 2.], vec![30., 20., 3.]]).unwrap();
+        assert!(result.merge_columns(1).yates_statistic.is_some());
+        result.set_yates_correction(false);
+        assert_eq!(result.merge_columns(1).yates_statistic, None);
+        assert_eq!(result.merge_low_expecteds().yates_statistic, None);
+    }
+}
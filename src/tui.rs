@@ -0,0 +1,1111 @@
+//! terminal helpers for reading input and editing tables
+
+use crossterm::{terminal, ExecutableCommand};
+use crossterm::event::{self, Event, KeyEvent, KeyCode, KeyModifiers, EnableBracketedPaste, DisableBracketedPaste};
+use costottorama::{text, back, style};
+use std::io;
+
+use crate::{graphemes, import};
+
+/// clears screen and prints given title
+pub fn display_title(text: &str) {
+    io::stdout().execute(crossterm::cursor::MoveTo(0,0)).unwrap();
+    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+   
+    println!(
+        "{}{}{} {text} {}\n", 
+        style::BOLD, 
+        back::WHITE,
+        text::BLACK,
+        style::RESET_ALL
+    );
+}
+
+/// continually asks the user for input until they enter a valid integer
+pub fn int_input(prompt: &str) -> u32 {
+    println!("{prompt}\n");
+    loop {
+        io::stdout().execute(crossterm::cursor::MoveUp(1)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::CurrentLine)).unwrap();
+
+        let mut user_input: String = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim().parse() {
+            Ok(num) => {return num},
+            Err(_) => continue
+        }
+    }
+}
+
+/// int input which clears after the input is given
+pub fn clearing_int_input(prompt: &str) -> u32 {
+    let output = int_input(prompt);
+    io::stdout().execute(crossterm::cursor::MoveUp(2)).unwrap();
+    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+    output
+}
+
+/// asks for a number from `min` to `max` until one is given, then clears it
+pub fn range_input(prompt: &str, min: u32, max: u32) -> u32 {
+    loop {
+        let choice = clearing_int_input(prompt);
+        if min <= choice && choice <= max {
+            return choice;
+        }
+    }
+}
+
+/// continually asks the user for input until they enter a valid number
+pub fn float_input(prompt: &str) -> f64 {
+    println!("{prompt}\n");
+    loop {
+        io::stdout().execute(crossterm::cursor::MoveUp(1)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::CurrentLine)).unwrap();
+
+        let mut user_input: String = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim().parse() {
+            Ok(num) => {return num},
+            Err(_) => continue
+        }
+    }
+}
+
+/// asks for a line of text, clearing it once it's given
+pub fn clearing_text_input(prompt: &str) -> String {
+    println!("{prompt}");
+
+    let mut user_input: String = String::new();
+    io::stdin().read_line(&mut user_input).unwrap();
+
+    io::stdout().execute(crossterm::cursor::MoveUp(2)).unwrap();
+    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+    user_input.trim().to_string()
+}
+
+/// asks a yes or no question, clearing it once it's answered.
+/// anything other than y or n gives the default
+pub fn yes_no_input(prompt: &str, default: bool) -> bool {
+    match clearing_text_input(prompt).to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default
+    }
+}
+
+/// asks for a percentage strictly between 0 and 100
+pub fn percentage_input(prompt: &str) -> f64 {
+    loop {
+        let percentage = float_input(prompt);
+        io::stdout().execute(crossterm::cursor::MoveUp(2)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+
+        if 0. < percentage && percentage < 100. {
+            return percentage;
+        }
+    }
+}
+
+/// pause current thread and return which key the
+/// user presses when they dos
+pub fn get_key_pressed() -> KeyCode {
+    match event::read().unwrap() {
+        Event::Key(KeyEvent {
+            code: c,
+            ..
+        }) => c,
+        _ => KeyCode::Null
+    }
+}
+
+/// a problem stopping a table from being used, with the cells causing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableError {
+    /// the (row, column) of each cell at fault, where (0, 0) is the top left label
+    pub cells: Vec<(usize, usize)>,
+    pub message: String,
+}
+
+impl TableError {
+    /// a problem with the cell at `pos`, where (0, 0) is the top left label
+    pub fn at(pos: (usize, usize), message: String) -> TableError {
+        TableError { cells: vec![pos], message }
+    }
+
+    /// a problem with the table as a whole rather than any one cell
+    pub fn whole(message: String) -> TableError {
+        TableError { cells: Vec::new(), message }
+    }
+}
+
+/// parses every cell of the table with `parse`, which is given the row the cell is in,
+/// returning a problem for each cell it can't parse
+pub fn parse_cells<T>(
+    table: &[Vec<String>],
+    parse: impl Fn(usize, &str) -> Result<T, String>
+) -> Result<Vec<Vec<T>>, Vec<TableError>> {
+    let mut parsed = Vec::with_capacity(table.len());
+    let mut errors = Vec::new();
+    for (i, cells) in table.iter().enumerate() {
+        let mut row = Vec::with_capacity(cells.len());
+        for (j, cell) in cells.iter().enumerate() {
+            match parse(i, cell) {
+                Ok(cell) => row.push(cell),
+                Err(error) => errors.push(TableError::at((i+1, j+1), error))
+            }
+        }
+        parsed.push(row);
+    }
+
+    if errors.is_empty() {Ok(parsed)} else {Err(errors)}
+}
+
+/// parses every column label with `parse`, returning a problem for each label it can't parse
+pub fn parse_column_labels<T>(
+    column_labels: &[String],
+    parse: impl Fn(&str) -> Result<T, String>
+) -> Result<Vec<T>, Vec<TableError>> {
+    let mut parsed = Vec::with_capacity(column_labels.len());
+    let mut errors = Vec::new();
+    for (j, label) in column_labels.iter().enumerate() {
+        match parse(label) {
+            Ok(label) => parsed.push(label),
+            Err(error) => errors.push(TableError::at((0, j+1), error))
+        }
+    }
+
+    if errors.is_empty() {Ok(parsed)} else {Err(errors)}
+}
+
+/// both results if they're both fine, otherwise the problems with either of them
+pub fn both<A, B>(a: Result<A, Vec<TableError>>, b: Result<B, Vec<TableError>>) -> Result<(A, B), Vec<TableError>> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok((a, b)),
+        (Err(mut a), Err(b)) => {a.extend(b); Err(a)},
+        (Err(errors), _) | (_, Err(errors)) => Err(errors)
+    }
+}
+
+/// something the user does while editing a table
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditInput {
+    /// a key pressed, along with any modifiers (like ctrl) held down with it
+    Key(KeyCode, KeyModifiers),
+    /// text pasted into the terminal
+    Paste(String),
+}
+
+/// pause current thread and return the key the user
+/// presses or the text they paste
+pub fn get_edit_input() -> EditInput {
+    match event::read().unwrap() {
+        Event::Key(KeyEvent {
+            code,
+            modifiers,
+            ..
+        }) => EditInput::Key(code, modifiers),
+        Event::Paste(text) => EditInput::Paste(text),
+        _ => EditInput::Key(KeyCode::Null, KeyModifiers::NONE)
+    }
+}
+
+/// which of a table's dimensions can be changed while it's being edited
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resizing {
+    pub rows: bool,
+    pub columns: bool,
+}
+
+impl Resizing {
+    pub const COLUMNS: Resizing = Resizing { rows: false, columns: true };
+    pub const ROWS_AND_COLUMNS: Resizing = Resizing { rows: true, columns: true };
+}
+
+/// how many columns wide the terminal is, or 80 if it can't be told
+pub fn terminal_width() -> usize {
+    match terminal::size() {
+        Ok((columns, _)) if columns > 0 => columns as usize,
+        _ => 80
+    }
+}
+
+/// how a table too wide for the terminal is shown, always with the row labels down the side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// the columns are split over as many blocks, one under another, as it takes
+    Wrap,
+    /// only the columns that fit from this one on are shown, for scrolling along while editing
+    Scroll(usize),
+}
+
+/// how many terminal columns the text of `cell` takes up. cells are lined up by this rather than
+/// their length, as characters like "男" are twice as wide and ones like combining accents take up
+/// none. a text cursor at the end of the cell at `pos` takes up an extra space
+fn cell_width(cell: (usize, usize), text: &str, pos: (usize, usize), text_cursor: Option<usize>) -> usize {
+    let width = graphemes::width(text);
+    // a cursor at the end (or past it) takes a space of its own
+    if cell == pos && text_cursor.is_some_and(|cursor| cursor >= graphemes::count(text)) {width + 1} else {width}
+}
+
+/// how many terminal columns the row labels and each column of the table take up
+fn column_widths<T: std::fmt::Display>(
+    table: &[Vec<T>],
+    column_labels: &[String],
+    row_labels: &[String],
+    pos: (usize, usize),
+    text_cursor: Option<usize>,
+) -> (usize, Vec<usize>) {
+    let width = |cell: (usize, usize), text: &str| cell_width(cell, text, pos, text_cursor);
+
+    let row_label_width = row_labels.iter().enumerate().map(|(row, label)| width((row, 0), label)).max().unwrap_or(0);
+    let mut widths: Vec<usize> = column_labels.iter().enumerate().map(|(j, label)| width((0, j + 1), label)).collect();
+    for (i, row) in table.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            widths[j] = widths[j].max(width((i + 1, j + 1), &cell.to_string()));
+        }
+    }
+
+    (row_label_width, widths)
+}
+
+/// how many columns from `first` on fit in `available` terminal columns, always at least one,
+/// with a space after each
+fn fitting_columns(widths: &[usize], first: usize, available: usize) -> usize {
+    let mut used = 0;
+    let mut count = 0;
+    for width in widths[first..].iter() {
+        if count > 0 && used + width + 1 > available {break}
+        used += width + 1;
+        count += 1;
+    }
+
+    count
+}
+
+/// the first column to show so that column `cursor` (of the table, from 0) is in view,
+/// scrolling along from `first` as little as possible
+fn scroll_to_column(widths: &[usize], available: usize, first: usize, cursor: usize) -> usize {
+    let mut first = first.min(widths.len().saturating_sub(1));
+    if cursor < first {
+        return cursor;
+    }
+    while first < cursor && first + fitting_columns(widths, first, available) <= cursor {
+        first += 1;
+    }
+
+    first
+}
+
+/// the terminal columns left for the table's columns beside the row labels and their " │ "
+fn available_width(row_label_width: usize) -> usize {
+    terminal_width().saturating_sub(row_label_width + 3)
+}
+
+/// the first column to show while editing the table with the cursor at `pos`, so that it
+/// stays in view as it moves, scrolling along from `first` as little as possible
+pub fn scroll_table(
+    table: &[Vec<String>],
+    column_labels: &[String],
+    row_labels: &[String],
+    pos: (usize, usize),
+    text_cursor: Option<usize>,
+    first: usize,
+) -> usize {
+    let (row_label_width, widths) = column_widths(table, column_labels, row_labels, pos, text_cursor);
+    // the row labels stay where they are, so don't need scrolling to
+    let cursor = if pos.1 == 0 {first} else {pos.1 - 1};
+    scroll_to_column(&widths, available_width(row_label_width), first, cursor)
+}
+
+/// how [`print_table`] shows a table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableDisplay<'a> {
+    /// the cell that's marked, where (0, 0) is the top left label
+    pub pos: (usize, usize),
+    /// (row, column) cells of the table to highlight
+    pub highlighted: &'a [(usize, usize)],
+    /// cells (positioned like `pos`) to show in red
+    pub invalid: &'a [(usize, usize)],
+    /// the character the text cursor is before, if the cell at `pos` is being edited
+    pub text_cursor: Option<usize>,
+    /// how a table wider than the terminal is shown
+    pub overflow: Overflow,
+}
+
+/// prints the table with its labels, marked, highlighted and shown as `display` says.
+/// each column is as wide as its widest cell. returns how many lines were printed
+pub fn print_table<T: std::fmt::Display>(
+    table: &[Vec<T>], 
+    column_labels: &[String], 
+    row_labels: &[String], 
+    display: TableDisplay,
+) -> u16 {
+    let TableDisplay { pos, highlighted, invalid, text_cursor, overflow } = display;
+    if row_labels.len() != table.len() + 1 {
+        panic!("row lengths do not match");
+    }
+    if column_labels.len() != table.first().map_or(0, |row| row.len()) {
+        panic!("column lengths do not match");
+    }
+
+    let (row_label_width, widths) = column_widths(table, column_labels, row_labels, pos, text_cursor);
+    let available = available_width(row_label_width);
+    let width = |cell: (usize, usize), text: &str| cell_width(cell, text, pos, text_cursor);
+
+    // the columns shown in each block
+    let mut blocks = Vec::new();
+    match overflow {
+        Overflow::Wrap => {
+            let mut first = 0;
+            while first < widths.len() {
+                let count = fitting_columns(&widths, first, available);
+                blocks.push(first..first + count);
+                first += count;
+            }
+            // a table without any columns still has its row labels
+            if blocks.is_empty() {
+                blocks.push(0..0);
+            }
+        },
+        Overflow::Scroll(first) => {
+            let first = first.min(widths.len().saturating_sub(1));
+            blocks.push(first..first + fitting_columns(&widths, first, available));
+        }
+    }
+
+    let pos_style = &format!("{}{}{}",
+        back::WHITE, 
+        text::BLACK, 
+        style::BOLD,
+    );
+    let reset_pos_style = &format!("{}{}{}", back::RESET, text::RESET, style::RESET_ALL);
+    let highlight_style = &format!("{}{}", back::YELLOW, text::BLACK);
+    let invalid_style = &format!("{}{}", back::RED, text::WHITE);
+    let text_cursor_style = &format!("{}{}", back::BLACK, text::WHITE);
+
+    // the text of a cell, with the text cursor shown in it if it's being edited
+    let cell_text = |cell: (usize, usize), text: &str| -> String {
+        match text_cursor {
+            Some(cursor) if cell == pos => {
+                let graphemes = graphemes::graphemes(text);
+                let cursor = cursor.min(graphemes.len());
+                let before: String = graphemes[..cursor].concat();
+                let after: String = graphemes.iter().skip(cursor + 1).copied().collect();
+                format!("{before}{text_cursor_style}{}{pos_style}{after}", graphemes.get(cursor).unwrap_or(&" "))
+            },
+            _ => text.to_string()
+        }
+    };
+
+    let mut lines = 0;
+    for (block, columns) in blocks.iter().enumerate() {
+        if block > 0 {
+            println!();
+            lines += 1;
+        }
+
+        for row in 0..table.len() + 1 {
+            let at_pos = pos.0 == row && pos.1 == 0;
+            let is_invalid = invalid.contains(&(row, 0));
+            print!(
+                "{}{}{}{}{} │ {}", 
+                if row == 0 {style::UNDERLINED} else {style::RESET_UNDERLINED},
+                if at_pos {pos_style} else if is_invalid {invalid_style} else {""},
+                cell_text((row, 0), &row_labels[row]), 
+                " ".repeat(row_label_width - width((row, 0), &row_labels[row])),
+                if at_pos || is_invalid {reset_pos_style} else {""},
+                style::RESET_ALL
+            );
+            if row == 0 {
+                for i in columns.clone() {
+                    let column = &column_labels[i];
+                    let at_pos = pos.0 == 0 && pos.1  == i + 1;
+                    let is_invalid = invalid.contains(&(0, i + 1));
+                    print!(
+                        "{}{}{}{}{} {}", 
+                        style::UNDERLINED,
+                        if at_pos {pos_style} else if is_invalid {invalid_style} else {""},
+                        cell_text((0, i + 1), column),
+                        " ".repeat(widths[i] - width((0, i + 1), column)),
+                        if at_pos || is_invalid {reset_pos_style} else {""},
+                        style::RESET_ALL
+                    );
+                }
+            }
+            else {
+                for i in columns.clone() {
+                    let at_pos = pos.0 == row && pos.1 == i + 1;
+                    let is_highlighted = highlighted.contains(&(row-1, i));
+                    let is_invalid = invalid.contains(&(row, i + 1));
+                    let column = table[row-1][i].to_string();
+                    print!("{}{}{}{} ", 
+                        if at_pos {pos_style} else if is_invalid {invalid_style} else if is_highlighted {highlight_style} else {back::LIGHT_BLACK},
+                        cell_text((row, i + 1), &column),
+                        " ".repeat(widths[i] - width((row, i + 1), &column)),
+                        if at_pos || is_invalid || is_highlighted {reset_pos_style} else {back::RESET}
+                    );
+                }
+            }
+            println!();
+            lines += 1;
+        }
+    }
+
+    // say where the view is when scrolling hides some of the columns
+    if let (Overflow::Scroll(_), Some(shown)) = (overflow, blocks.first()) {
+        if shown.len() < widths.len() {
+            println!(
+                "{}{} columns {} to {} of {} {}{}",
+                text::MAGENTA,
+                if shown.start > 0 {"◀"} else {" "},
+                shown.start + 1,
+                shown.end,
+                widths.len(),
+                if shown.end < widths.len() {"▶"} else {" "},
+                text::RESET
+            );
+            lines += 1;
+        }
+    }
+
+    lines
+}
+
+/// the text of the cell at `pos`, where (0, 0) is the top left label
+fn cell_mut<'a>(
+    table: &'a mut [Vec<String>],
+    column_labels: &'a mut [String],
+    row_labels: &'a mut [String],
+    pos: (usize, usize)
+) -> &'a mut String {
+    match pos {
+        (r, 0) => &mut row_labels[r],
+        (0, c) => &mut column_labels[c-1],
+        (r, c) => &mut table[r-1][c-1]
+    }
+}
+
+/// removes the character before the text cursor in the cell at `current_pos`,
+/// returning where the text cursor is left. the cursor counts graphemes rather than bytes
+/// or chars, so an accented letter is removed along with its accent
+fn delete_item_in_table(
+    table: &mut [Vec<String>], 
+    column_labels: &mut [String],
+    row_labels: &mut [String], 
+    current_pos: (usize, usize),
+    cursor: usize
+) -> usize {
+    if cursor == 0 {return 0}
+
+    let cell = cell_mut(table, column_labels, row_labels, current_pos);
+    let (start, end) = (graphemes::byte_index(cell, cursor - 1), graphemes::byte_index(cell, cursor));
+    cell.replace_range(start..end, "");
+    cursor - 1
+}
+
+/// removes the character after the text cursor in the cell at `current_pos`
+fn delete_after_cursor(
+    table: &mut [Vec<String>], 
+    column_labels: &mut [String],
+    row_labels: &mut [String], 
+    current_pos: (usize, usize),
+    cursor: usize
+) {
+    let cell = cell_mut(table, column_labels, row_labels, current_pos);
+    let (start, end) = (graphemes::byte_index(cell, cursor), graphemes::byte_index(cell, cursor + 1));
+    cell.replace_range(start..end, "");
+}
+
+/// types `ch` at the text cursor in the cell at `current_pos`, returning where the text cursor moves to.
+/// a combining mark joins onto the character before it, so leaves the cursor where it is
+fn add_to_table(
+    table: &mut [Vec<String>], 
+    column_labels: &mut [String],
+    row_labels: &mut [String], 
+    current_pos: (usize, usize),
+    cursor: usize,
+    ch: char,
+) -> usize {
+    let cell = cell_mut(table, column_labels, row_labels, current_pos);
+    let index = graphemes::byte_index(cell, cursor);
+    cell.insert(index, ch);
+    graphemes::count(&cell[..index + ch.len_utf8()])
+}
+
+/// replaces the cell at `pos`, where (0, 0) is the top left label
+fn set_cell(
+    table: &mut [Vec<String>],
+    column_labels: &mut [String],
+    row_labels: &mut [String],
+    pos: (usize, usize),
+    value: &str
+) {
+    *cell_mut(table, column_labels, row_labels, pos) = value.to_string();
+}
+
+/// fills the cells along and down from `pos` with each row of `text` (separated by tabs,
+/// commas, semicolons or spaces), adding rows and columns to fit them where `resizing` allows.
+/// a single value is typed in at the text cursor instead, returning where the text cursor moves to
+fn paste_into_table(
+    table: &mut Vec<Vec<String>>,
+    column_labels: &mut Vec<String>,
+    row_labels: &mut Vec<String>,
+    pos: (usize, usize),
+    cursor: usize,
+    text: &str,
+    resizing: Resizing
+) -> Option<usize> {
+    let rows = import::split_rows(text);
+    if rows.len() == 1 && rows[0].len() == 1 {
+        let mut cursor = cursor;
+        for ch in rows[0][0].chars() {
+            cursor = add_to_table(table, column_labels, row_labels, pos, cursor, ch);
+        }
+        return Some(cursor);
+    }
+
+    for (i, values) in rows.iter().enumerate() {
+        let row = pos.0 + i;
+        if row > table.len() {
+            if !resizing.rows {break}
+            insert_row(table, row_labels, (table.len(), 0));
+        }
+        for (j, value) in values.iter().enumerate() {
+            let column = pos.1 + j;
+            if column > table[0].len() {
+                if !resizing.columns {break}
+                let columns = table[0].len();
+                insert_column(table, column_labels, (0, columns));
+            }
+            set_cell(table, column_labels, row_labels, (row, column), value);
+        }
+    }
+    None
+}
+
+/// prints `hints` like "[esc] to finish" after "press", with the keys in blue and
+/// as many hints to a line as fit in the terminal, returning how many lines were printed
+fn print_key_hints(hints: &[String]) -> u16 {
+    let width = terminal_width();
+    let mut lines = vec![String::from("press")];
+    for (i, hint) in hints.iter().enumerate() {
+        let hint = if i < hints.len() - 1 {format!("{hint},")} else {hint.clone()};
+        let line = lines.last_mut().unwrap();
+        if graphemes::width(line) + graphemes::width(&hint) + 1 > width && line != "press" {
+            lines.push(hint);
+        } else {
+            *line += &format!(" {hint}");
+        }
+    }
+
+    for line in lines.iter() {
+        println!("{}", line.replace('[', &format!("{}[", text::LIGHT_BLUE)).replace(']', &format!("]{}", text::RESET)));
+    }
+    lines.len() as u16
+}
+
+/// how many terminal lines the text takes up once it wraps at the terminal's `width`
+fn wrapped_height(text: &str, width: usize) -> u16 {
+    text.lines()
+        .map(|line| graphemes::width(line).div_ceil(width.max(1)).max(1) as u16)
+        .sum::<u16>()
+        .max(1)
+}
+
+/// the table and its labels at one point while they're being edited, with where the cursor was
+#[derive(Debug, Clone, PartialEq)]
+struct TableState {
+    table: Vec<Vec<String>>,
+    column_labels: Vec<String>,
+    row_labels: Vec<String>,
+    pos: (usize, usize),
+}
+
+impl TableState {
+    fn of(table: &[Vec<String>], column_labels: &[String], row_labels: &[String], pos: (usize, usize)) -> TableState {
+        TableState { table: table.to_vec(), column_labels: column_labels.to_vec(), row_labels: row_labels.to_vec(), pos }
+    }
+
+    /// whether the table or its labels are different, wherever the cursor is
+    fn differs_from(&self, table: &[Vec<String>], column_labels: &[String], row_labels: &[String]) -> bool {
+        self.table != *table || self.column_labels != *column_labels || self.row_labels != *row_labels
+    }
+}
+
+/// the states a table was in before each edit that can be undone, and after each undo that can be redone
+#[derive(Debug, Default)]
+struct History {
+    undo: Vec<TableState>,
+    redo: Vec<TableState>,
+}
+
+impl History {
+    /// keeps the state from `before` an edit so it can be undone, as long as the edit changed anything.
+    /// a new edit means nothing that was undone can be redone
+    fn record(&mut self, before: TableState, table: &[Vec<String>], column_labels: &[String], row_labels: &[String]) {
+        if before.differs_from(table, column_labels, row_labels) {
+            self.undo.push(before);
+            self.redo.clear();
+        }
+    }
+
+    /// the state before the last edit, keeping the `current` one to redo
+    fn undo(&mut self, current: TableState) -> Option<TableState> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// the state before the last undo, keeping the `current` one to undo again
+    fn redo(&mut self, current: TableState) -> Option<TableState> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+}
+
+/// the number the labels start from, if they're numbered one after another (like 0, 1, 2)
+fn consecutive_start(labels: &[String]) -> Option<usize> {
+    let start = labels.first()?.trim().parse::<usize>().ok()?;
+    for (i, label) in labels.iter().enumerate() {
+        if label.trim().parse::<usize>().ok()? != start + i {
+            return None;
+        }
+    }
+
+    Some(start)
+}
+
+/// adds a blank row to the table after the row at `pos` (or at the top from the column labels)
+fn insert_row(table: &mut Vec<Vec<String>>, row_labels: &mut Vec<String>, pos: (usize, usize)) {
+    let columns = table[0].len();
+    table.insert(pos.0, vec![String::from(""); columns]);
+    row_labels.insert(pos.0 + 1, String::from("---"));
+}
+
+/// removes the row at `pos`, as long as it isn't the column labels or the last row left
+fn remove_row(table: &mut Vec<Vec<String>>, row_labels: &mut Vec<String>, pos: (usize, usize)) {
+    if pos.0 == 0 || table.len() <= 1 {return}
+
+    table.remove(pos.0 - 1);
+    row_labels.remove(pos.0);
+}
+
+/// adds a blank column to the table after the column at `pos` (or at the start from the row labels).
+/// the other columns keep their labels, so counts stay labelled by their values. a column added
+/// just past either end of columns numbered one after another carries on the numbering
+fn insert_column(table: &mut [Vec<String>], column_labels: &mut Vec<String>, pos: (usize, usize)) {
+    let label = match consecutive_start(column_labels) {
+        Some(start) if pos.1 == column_labels.len() => (start + pos.1).to_string(),
+        Some(start) if pos.1 == 0 && start > 0 => (start - 1).to_string(),
+        _ => String::from("---"),
+    };
+    for row in table.iter_mut() {
+        row.insert(pos.1, String::from(""));
+    }
+    column_labels.insert(pos.1, label);
+}
+
+/// removes the column at `pos`, as long as it isn't the row labels or the last column left
+fn remove_column(table: &mut [Vec<String>], column_labels: &mut Vec<String>, pos: (usize, usize)) {
+    if pos.1 == 0 || table[0].len() <= 1 {return}
+
+    for row in table.iter_mut() {
+        row.remove(pos.1 - 1);
+    }
+    column_labels.remove(pos.1 - 1);
+}
+
+/// keeps the text cursor within the text of the cell being edited, which can
+/// change under it (such as when a paste fills it in)
+fn clamp_text_cursor(text_cursor: Option<usize>, cell: &str) -> Option<usize> {
+    text_cursor.map(|cursor| cursor.min(graphemes::count(cell)))
+}
+
+/// lets the user edit the table and its labels until they finish with one `read` accepts,
+/// returning what it reads from it. if `read` finds problems, the cells causing them are
+/// shown in red with the first problem below the table, and the cursor moves to the first of them.
+/// rows and columns can be added and removed at the cursor where `resizing` allows it, and
+/// whole rows can be typed in or pasted from a spreadsheet. every edit can be undone and redone.
+/// typing (or [f2], [home] or [end]) edits the cell with a text cursor that moves within it
+pub fn edit_table<T>(
+    table: &mut Vec<Vec<String>>,
+    column_labels: &mut Vec<String>,
+    row_labels: &mut Vec<String>,
+    resizing: Resizing,
+    read: impl Fn(&Vec<Vec<String>>, &Vec<String>, &Vec<String>) -> Result<T, Vec<TableError>>
+) -> T {
+    let mut current_pos = (1, 1);
+    // where the text cursor is in the current cell, if it's being edited
+    let mut text_cursor: Option<usize> = None;
+    // the first column shown, when the table is too wide for the terminal
+    let mut first_column = 0;
+    // problems are only shown once the user has tried to finish
+    let mut errors: Option<Vec<TableError>> = None;
+    let mut history = History::default();
+
+    io::stdout().execute(crossterm::cursor::Hide).unwrap();
+    // not every terminal supports bracketed paste, and pasted text is typed in without it
+    let _ = io::stdout().execute(EnableBracketedPaste);
+
+    loop {
+        let invalid: Vec<(usize, usize)> = errors.iter().flatten().flat_map(|error| error.cells.iter().copied()).collect();
+        first_column = scroll_table(table, column_labels, row_labels, current_pos, text_cursor, first_column);
+        let mut lines = print_table(table, column_labels, row_labels, TableDisplay {
+            pos: current_pos,
+            highlighted: &[],
+            invalid: &invalid,
+            text_cursor,
+            overflow: Overflow::Scroll(first_column),
+        });
+        if let Some(errors) = &errors {
+            let more = match errors.len() {
+                1 => String::from(""),
+                2 => String::from(" (and 1 more problem)"),
+                count => format!(" (and {} more problems)", count - 1)
+            };
+            let message = format!("{}{more}", errors[0].message);
+            println!("\n{}{message}{}", text::RED, text::RESET);
+            lines += 1 + wrapped_height(&message, terminal_width());
+        }
+        let mut hints = if text_cursor.is_some() {
+            vec![String::from("[esc] to stop editing the cell"), String::from("[home]/[end] to move along it")]
+        } else {
+            vec![
+                String::from("[esc] to finish"),
+                String::from("[tab]/[shift+tab]/[enter] to move"),
+                String::from("[del] to clear a cell"),
+                String::from("[ctrl+t] to type in a whole row (or paste rows in)")
+            ]
+        };
+        hints.push(String::from("[ctrl+z]/[ctrl+y] to undo/redo"));
+        if resizing.rows {
+            hints.push(String::from("[ctrl+r]/[ctrl+d] to add/delete a row"));
+        }
+        if resizing.columns {
+            hints.push(String::from("[ctrl+n]/[ctrl+x] to add/delete a column"));
+        }
+        println!();
+        lines += print_key_hints(&hints) + 1;
+
+        let before = TableState::of(table, column_labels, row_labels, current_pos);
+        let cell_length = graphemes::count(cell_mut(table, column_labels, row_labels, current_pos));
+        let (key_pressed, modifiers) = match get_edit_input() {
+            EditInput::Key(key_pressed, modifiers) => (key_pressed, modifiers),
+            EditInput::Paste(text) => {
+                let cursor = text_cursor.unwrap_or(cell_length);
+                text_cursor = paste_into_table(table, column_labels, row_labels, current_pos, cursor, &text, resizing);
+                (KeyCode::Null, KeyModifiers::NONE)
+            }
+        };
+        let previous_pos = current_pos;
+        match key_pressed {
+            KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                io::stdout().execute(crossterm::cursor::Show).unwrap();
+                let row = clearing_text_input("type the values from the cursor along the row, separated by spaces, commas or tabs:");
+                io::stdout().execute(crossterm::cursor::Hide).unwrap();
+                let cursor = text_cursor.unwrap_or(cell_length);
+                text_cursor = paste_into_table(table, column_labels, row_labels, current_pos, cursor, &row, resizing);
+            },
+            KeyCode::Char(ch) if modifiers.contains(KeyModifiers::CONTROL) => match ch {
+                'z' | 'y' => {
+                    let state = if ch == 'z' {history.undo(before.clone())} else {history.redo(before.clone())};
+                    if let Some(state) = state {
+                        (*table, *column_labels, *row_labels) = (state.table, state.column_labels, state.row_labels);
+                        current_pos = state.pos;
+                        text_cursor = None;
+                    }
+                },
+                'r' if resizing.rows => {
+                    insert_row(table, row_labels, current_pos);
+                    current_pos = (current_pos.0 + 1, current_pos.1);
+                    text_cursor = None;
+                },
+                'd' if resizing.rows => {
+                    remove_row(table, row_labels, current_pos);
+                    current_pos = (current_pos.0.min(table.len()), current_pos.1);
+                    text_cursor = None;
+                },
+                'n' if resizing.columns => {
+                    insert_column(table, column_labels, current_pos);
+                    current_pos = (current_pos.0, current_pos.1 + 1);
+                    text_cursor = None;
+                },
+                'x' if resizing.columns => {
+                    remove_column(table, column_labels, current_pos);
+                    current_pos = (current_pos.0, current_pos.1.min(table[0].len()));
+                    text_cursor = None;
+                },
+                _ => {}
+            },
+            KeyCode::Up if current_pos.0 > 0 => current_pos = (current_pos.0 - 1, current_pos.1),
+            KeyCode::Down | KeyCode::Enter => {
+                if current_pos.0 < table.len() {current_pos.0 += 1}
+                text_cursor = None;
+            },
+            KeyCode::Left => match text_cursor {
+                Some(cursor) => text_cursor = Some(cursor.saturating_sub(1)),
+                None => if current_pos.1 > 0 {current_pos = (current_pos.0, current_pos.1 - 1)}
+            },
+            KeyCode::Right => match text_cursor {
+                Some(cursor) => text_cursor = Some((cursor + 1).min(cell_length)),
+                None => if current_pos.1 < table[0].len() {current_pos = (current_pos.0, current_pos.1 + 1)}
+            },
+            // tab goes along the row then on to the start of the next, and shift+tab back
+            KeyCode::Tab => {
+                if current_pos.1 < table[0].len() {current_pos = (current_pos.0, current_pos.1 + 1)}
+                else if current_pos.0 < table.len() {current_pos = (current_pos.0 + 1, 1)}
+            },
+            KeyCode::BackTab => {
+                if current_pos.1 > 1 {current_pos = (current_pos.0, current_pos.1 - 1)}
+                else if current_pos.0 > 0 {current_pos = (current_pos.0 - 1, table[0].len())}
+            },
+            KeyCode::Home => text_cursor = Some(0),
+            KeyCode::End | KeyCode::F(2) => text_cursor = Some(cell_length),
+            KeyCode::Delete => match text_cursor {
+                Some(cursor) => delete_after_cursor(table, column_labels, row_labels, current_pos, cursor),
+                None => set_cell(table, column_labels, row_labels, current_pos, "")
+            },
+            KeyCode::Backspace => {
+                let cursor = text_cursor.unwrap_or(cell_length);
+                text_cursor = Some(delete_item_in_table(table, column_labels, row_labels, current_pos, cursor));
+            },
+            KeyCode::Char(ch) => {
+                let cursor = text_cursor.unwrap_or(cell_length);
+                text_cursor = Some(add_to_table(table, column_labels, row_labels, current_pos, cursor, ch));
+            },
+            KeyCode::Esc if text_cursor.is_some() => text_cursor = None,
+            KeyCode::Esc => match read(table, column_labels, row_labels) {
+                Ok(read) => {
+                    let _ = io::stdout().execute(DisableBracketedPaste);
+                    io::stdout().execute(crossterm::cursor::Show).unwrap();
+                    io::stdout().execute(crossterm::cursor::MoveUp(lines)).unwrap();
+                    io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+                    return read;
+                },
+                Err(read_errors) => {
+                    if let Some(cell) = read_errors.iter().flat_map(|error| error.cells.iter()).next() {
+                        current_pos = *cell;
+                    }
+                    errors = Some(read_errors);
+                }
+            },
+            _ => {}
+        }
+        // moving to another cell stops editing this one
+        if current_pos != previous_pos {
+            text_cursor = None;
+        }
+        text_cursor = clamp_text_cursor(text_cursor, cell_mut(table, column_labels, row_labels, current_pos));
+        let undoing = modifiers.contains(KeyModifiers::CONTROL) && matches!(key_pressed, KeyCode::Char('z' | 'y'));
+        if !undoing {
+            history.record(before, table, column_labels, row_labels);
+        }
+        // once shown, problems are kept up to date as they're fixed
+        if errors.is_some() && key_pressed != KeyCode::Esc {
+            errors = read(table, column_labels, row_labels).err();
+        }
+        
+        io::stdout().execute(crossterm::cursor::MoveUp(lines)).unwrap();
+        io::stdout().execute(terminal::Clear(terminal::ClearType::FromCursorDown)).unwrap();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problems_with_cells_and_labels() {
+        let table = vec![vec![String::from("4"), String::from("x")], vec![String::from("-1"), String::from("2")]];
+        let parse = |_, cell: &str| match cell.parse::<u32>() {
+            Ok(count) => Ok(count),
+            Err(_) => Err(format!("'{cell}' is not a count"))
+        };
+        let errors = parse_cells(&table, parse).unwrap_err();
+        assert_eq!(errors.iter().map(|error| error.cells[0]).collect::<Vec<_>>(), vec![(1, 2), (2, 1)]);
+        assert_eq!(errors[0].message, "'x' is not a count");
+
+        let labels = vec![String::from("0"), String::from("one")];
+        let label_errors = parse_column_labels(&labels, |label| label.parse::<u32>().map_err(|_| String::from("not a value")));
+        assert_eq!(label_errors.clone().unwrap_err(), vec![TableError::at((0, 2), String::from("not a value"))]);
+        assert_eq!(both(label_errors, parse_cells(&table, parse)).unwrap_err().len(), 3);
+
+        let table = vec![vec![String::from("4"), String::from("2")]];
+        assert_eq!(parse_cells(&table, parse), Ok(vec![vec![4, 2]]));
+    }
+
+    #[test]
+    fn adding_and_removing_rows_and_columns() {
+        let mut table = vec![vec![String::from("5"), String::from("7")]];
+        let mut column_labels = vec![String::from("0"), String::from("1")];
+        let mut row_labels = vec![String::from("type"), String::from("Observed")];
+
+        // counts stay with their values, and numbering carries on past the last column
+        insert_column(&mut table, &mut column_labels, (1, 1));
+        assert_eq!(table, vec![vec!["5", "", "7"]]);
+        assert_eq!(column_labels, vec!["0", "---", "1"]);
+        remove_column(&mut table, &mut column_labels, (1, 2));
+        insert_column(&mut table, &mut column_labels, (1, 2));
+        assert_eq!(table, vec![vec!["5", "7", ""]]);
+        assert_eq!(column_labels, vec!["0", "1", "2"]);
+        remove_column(&mut table, &mut column_labels, (1, 3));
+        assert_eq!(table, vec![vec!["5", "7"]]);
+        assert_eq!(column_labels, vec!["0", "1"]);
+
+        insert_row(&mut table, &mut row_labels, (1, 1));
+        assert_eq!(table, vec![vec!["5", "7"], vec!["", ""]]);
+        assert_eq!(row_labels, vec!["type", "Observed", "---"]);
+        remove_row(&mut table, &mut row_labels, (1, 1));
+        remove_row(&mut table, &mut row_labels, (1, 1));
+        assert_eq!(table.len(), 1);
+
+        // pasted rows fill along and down from the cursor, adding columns to fit
+        let mut row_labels = vec![String::from("type"), String::from("Observed")];
+        paste_into_table(&mut table, &mut column_labels, &mut row_labels, (1, 2), 0, "12\t15\t9\r\n", Resizing::COLUMNS);
+        assert_eq!(table, vec![vec!["", "12", "15", "9"]]);
+        assert_eq!(column_labels, vec!["0", "1", "2", "3"]);
+        paste_into_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 0, "4 5\n6 7", Resizing::COLUMNS);
+        assert_eq!(table, vec![vec!["4", "5", "15", "9"]]);
+        assert_eq!(paste_into_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 1, "0", Resizing::COLUMNS), Some(2));
+        assert_eq!(table[0][0], "40");
+
+        let mut column_labels = vec![String::from("red"), String::from("blue")];
+        insert_column(&mut table, &mut column_labels, (0, 0));
+        assert_eq!(column_labels, vec!["---", "red", "blue"]);
+    }
+
+    #[test]
+    fn undoing_and_redoing_edits() {
+        let mut table = vec![vec![String::from("1")]];
+        let column_labels = vec![String::from("0")];
+        let row_labels = vec![String::from("type"), String::from("Observed")];
+        let mut history = History::default();
+
+        let before = TableState::of(&table, &column_labels, &row_labels, (1, 1));
+        history.record(before.clone(), &table, &column_labels, &row_labels);
+        assert!(history.undo.is_empty());
+
+        table[0][0] = String::from("12");
+        history.record(before.clone(), &table, &column_labels, &row_labels);
+        let edited = TableState::of(&table, &column_labels, &row_labels, (1, 1));
+        assert_eq!(history.undo(edited.clone()), Some(before.clone()));
+        assert_eq!(history.undo(before.clone()), None);
+        assert_eq!(history.redo(before.clone()), Some(edited));
+
+        // a new edit can't be redone past
+        history.undo(before.clone());
+        table[0][0] = String::from("2");
+        history.record(before, &table, &column_labels, &row_labels);
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn editing_at_the_text_cursor() {
+        let mut table = vec![vec![String::from("15")]];
+        let mut column_labels = vec![String::from("0")];
+        let mut row_labels = vec![String::from("type"), String::from("Observed")];
+
+        let cursor = add_to_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 1, '2');
+        assert_eq!((table[0][0].as_str(), cursor), ("125", 2));
+        let cursor = delete_item_in_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 1);
+        assert_eq!((table[0][0].as_str(), cursor), ("25", 0));
+        assert_eq!(delete_item_in_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 0), 0);
+        delete_after_cursor(&mut table, &mut column_labels, &mut row_labels, (1, 1), 0);
+        assert_eq!(table[0][0], "5");
+
+        add_to_table(&mut table, &mut column_labels, &mut row_labels, (0, 0), 0, 'λ');
+        add_to_table(&mut table, &mut column_labels, &mut row_labels, (0, 0), 1, ' ');
+        assert_eq!(row_labels[0], "λ type");
+        delete_after_cursor(&mut table, &mut column_labels, &mut row_labels, (0, 0), 6);
+        assert_eq!(row_labels[0], "λ type");
+    }
+
+    #[test]
+    fn editing_accented_and_cjk_labels() {
+        let mut table = vec![vec![String::from("")]];
+        let mut column_labels = vec![String::from("男性")];
+        let mut row_labels = vec![String::from("λ²"), String::from("Observed")];
+
+        // deleting after a multibyte character used to split it in half
+        assert_eq!(delete_item_in_table(&mut table, &mut column_labels, &mut row_labels, (0, 0), 2), 1);
+        assert_eq!(row_labels[0], "λ");
+        assert_eq!(delete_item_in_table(&mut table, &mut column_labels, &mut row_labels, (0, 1), 1), 0);
+        assert_eq!(column_labels[0], "性");
+
+        // an accent typed after a letter joins it, and is deleted with it
+        let cursor = add_to_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 0, 'e');
+        let cursor = add_to_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), cursor, '\u{301}');
+        assert_eq!(cursor, 1);
+        let cursor = add_to_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), cursor, 'x');
+        assert_eq!((table[0][0].as_str(), cursor), ("e\u{301}x", 2));
+        assert_eq!(delete_item_in_table(&mut table, &mut column_labels, &mut row_labels, (1, 1), 1), 0);
+        assert_eq!(table[0][0], "x");
+        delete_after_cursor(&mut table, &mut column_labels, &mut row_labels, (0, 1), 0);
+        assert_eq!(column_labels[0], "");
+    }
+
+    #[test]
+    fn scrolling_wide_tables() {
+        let table = vec![vec![String::from("12"); 30]];
+        let column_labels: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+        let row_labels = vec![String::from("type"), String::from("Observed")];
+        let (row_label_width, widths) = column_widths(&table, &column_labels, &row_labels, (1, 1), None);
+        assert_eq!(row_label_width, 8);
+        assert_eq!(widths[0], 2);
+
+        // each column takes up 3 terminal columns with the space after it
+        assert_eq!(fitting_columns(&widths, 0, 30), 10);
+        assert_eq!(fitting_columns(&widths, 25, 30), 5);
+        assert_eq!(fitting_columns(&[100, 2], 0, 30), 1);
+
+        // the view only moves once the cursor goes past either edge
+        assert_eq!(scroll_to_column(&widths, 30, 0, 9), 0);
+        assert_eq!(scroll_to_column(&widths, 30, 0, 10), 1);
+        assert_eq!(scroll_to_column(&widths, 30, 5, 7), 5);
+        assert_eq!(scroll_to_column(&widths, 30, 5, 2), 2);
+        assert_eq!(scroll_to_column(&widths[..3], 30, 20, 2), 2);
+
+        // a text cursor at the end of a cell makes its column wider
+        let (_, widths) = column_widths(&table, &column_labels, &row_labels, (1, 1), Some(2));
+        assert_eq!((widths[0], widths[1]), (3, 2));
+    }
+
+    #[test]
+    fn wrapped_heights() {
+        assert_eq!(wrapped_height("", 80), 1);
+        assert_eq!(wrapped_height(&"x".repeat(80), 80), 1);
+        assert_eq!(wrapped_height(&"x".repeat(81), 80), 2);
+        assert_eq!(wrapped_height("男性男性", 4), 2);
+        assert_eq!(wrapped_height("a\nb", 80), 2);
+    }
+
+    #[test]
+    fn tables_without_columns() {
+        let table: Vec<Vec<String>> = vec![vec![]];
+        let row_labels = vec![String::from("type"), String::from("Observed")];
+        for overflow in [Overflow::Wrap, Overflow::Scroll(3)] {
+            let display = TableDisplay { pos: (1, 0), highlighted: &[], invalid: &[], text_cursor: None, overflow };
+            assert_eq!(print_table(&table, &[], &row_labels, display), 2);
+        }
+    }
+
+    #[test]
+    fn text_cursor_stays_in_its_cell() {
+        // removing a column can leave the cursor past the end of the shorter cell now under it
+        let mut table = vec![vec![String::from("12345"), String::from("1")]];
+        let mut column_labels = vec![String::from("0"), String::from("1")];
+        let row_labels = vec![String::from("type"), String::from("Observed")];
+        remove_column(&mut table, &mut column_labels, (1, 1));
+        let cursor = clamp_text_cursor(Some(5), &table[0][0]);
+        assert_eq!(cursor, Some(1));
+        assert_eq!(clamp_text_cursor(None, "12"), None);
+
+        // and a cursor that's out of range is still drawn at the end of the cell
+        let display = TableDisplay { pos: (1, 1), highlighted: &[], invalid: &[], text_cursor: Some(5), overflow: Overflow::Wrap };
+        print_table(&table, &column_labels, &row_labels, display);
+    }
+}